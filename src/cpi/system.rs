@@ -0,0 +1,57 @@
+use pinocchio::{account_info::AccountInfo, instruction::Signer, pubkey::Pubkey, ProgramResult};
+use pinocchio_system::instructions::{Allocate, Assign, CreateAccount, Transfer};
+
+use crate::errors::MyProgramError;
+
+/// Checks that the account passed in the system program slot is the system program.
+#[inline(always)]
+pub fn check_system_program(info: &AccountInfo) -> ProgramResult {
+    if info.key() != &pinocchio_system::ID {
+        return Err(MyProgramError::WrongSystemProgram.into());
+    }
+    Ok(())
+}
+
+/// Creates a `space`-byte account owned by `owner` at a PDA, holding at least `lamports`.
+///
+/// `CreateAccount` fails once the address holds any lamports, and anyone can send
+/// lamports to an address. A pre-funded address is therefore topped up with
+/// `Transfer` and then set up with `Allocate` + `Assign`, signed by the PDA seeds.
+pub fn create_pda_account(
+    payer: &AccountInfo,
+    account: &AccountInfo,
+    space: usize,
+    lamports: u64,
+    owner: &Pubkey,
+    signers: &[Signer],
+) -> ProgramResult {
+    let current_lamports = account.lamports();
+
+    if current_lamports == 0 {
+        return CreateAccount {
+            from: payer,
+            to: account,
+            lamports,
+            space: space as u64,
+            owner,
+        }
+        .invoke_signed(signers);
+    }
+
+    if current_lamports < lamports {
+        Transfer {
+            from: payer,
+            to: account,
+            lamports: lamports - current_lamports,
+        }
+        .invoke()?;
+    }
+
+    Allocate {
+        account,
+        space: space as u64,
+    }
+    .invoke_signed(signers)?;
+
+    Assign { account, owner }.invoke_signed(signers)
+}
@@ -0,0 +1,321 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Signer},
+    program::{get_return_data, invoke_signed, slice_invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::errors::MyProgramError;
+
+/// SPL Token program.
+pub const TOKEN_PROGRAM_ID: Pubkey =
+    pinocchio_pubkey::from_str("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+/// SPL Token-2022 program.
+pub const TOKEN_2022_PROGRAM_ID: Pubkey =
+    pinocchio_pubkey::from_str("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// Length of a token account without extensions.
+pub const TOKEN_ACCOUNT_LEN: usize = 165;
+
+/// Length of a mint without extensions.
+pub const MINT_LEN: usize = 82;
+
+/// Offset of `decimals` in mint data.
+pub const MINT_DECIMALS_OFFSET: usize = 44;
+
+/// Maximum number of extra accounts forwarded to a transfer-hook program.
+pub const MAX_TRANSFER_HOOK_ACCOUNTS: usize = 16;
+
+/// Token-2022 extension type of the withheld fee on a token account.
+const TRANSFER_FEE_AMOUNT_EXTENSION: u16 = 2;
+
+#[inline(always)]
+pub fn is_token_program(program_id: &Pubkey) -> bool {
+    program_id == &TOKEN_PROGRAM_ID || program_id == &TOKEN_2022_PROGRAM_ID
+}
+
+/// Finds a Token-2022 extension in the TLV area of a token account or mint.
+///
+/// Extensions start after the base account length plus one account-type byte;
+/// data without extensions returns `None`.
+pub fn get_extension(data: &[u8], extension_type: u16) -> Option<&[u8]> {
+    let mut offset = TOKEN_ACCOUNT_LEN + 1;
+    while offset + 4 <= data.len() {
+        let ty = u16::from_le_bytes([data[offset], data[offset + 1]]);
+        let len = u16::from_le_bytes([data[offset + 2], data[offset + 3]]) as usize;
+        let start = offset + 4;
+        if start + len > data.len() {
+            return None;
+        }
+        if ty == extension_type {
+            return Some(&data[start..start + len]);
+        }
+        // Type 0 marks the uninitialized tail of the TLV area.
+        if ty == 0 {
+            return None;
+        }
+        offset = start + len;
+    }
+    None
+}
+
+/// Transfer fees withheld on a token account, zero if it has no fee extension.
+pub fn withheld_amount(data: &[u8]) -> u64 {
+    match get_extension(data, TRANSFER_FEE_AMOUNT_EXTENSION) {
+        Some(value) if value.len() >= 8 => {
+            let mut amount = [0u8; 8];
+            amount.copy_from_slice(&value[..8]);
+            u64::from_le_bytes(amount)
+        }
+        _ => 0,
+    }
+}
+
+/// Initialize a token account without requiring the rent sysvar.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The account to initialize
+///   1. `[]` The mint this account will be associated with
+pub struct InitializeAccount3<'a> {
+    /// New token account.
+    pub account: &'a AccountInfo,
+
+    /// Mint account.
+    pub mint: &'a AccountInfo,
+
+    /// Owner of the new token account.
+    pub owner: &'a Pubkey,
+
+    /// Token program owning the mint.
+    pub token_program: &'a Pubkey,
+}
+
+impl InitializeAccount3<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        let account_metas: [AccountMeta; 2] = [
+            AccountMeta::writable(self.account.key()),
+            AccountMeta::readonly(self.mint.key()),
+        ];
+
+        // instruction data
+        // -  [0]    : instruction discriminator
+        // -  [1..33]: owner
+        let mut instruction_data = [0; 33];
+        instruction_data[0] = 18;
+        instruction_data[1..33].copy_from_slice(self.owner);
+
+        let instruction = Instruction {
+            program_id: self.token_program,
+            accounts: &account_metas,
+            data: &instruction_data,
+        };
+
+        invoke_signed(&instruction, &[self.account, self.mint], &[])
+    }
+}
+
+/// Get the account size needed for a token account of the given mint,
+/// including the extensions the mint requires.
+///
+/// ### Accounts:
+///   0. `[]` The mint to calculate for
+pub struct GetAccountDataSize<'a> {
+    /// Mint account.
+    pub mint: &'a AccountInfo,
+
+    /// Token program owning the mint.
+    pub token_program: &'a Pubkey,
+}
+
+impl GetAccountDataSize<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> Result<usize, ProgramError> {
+        let account_metas: [AccountMeta; 1] = [AccountMeta::readonly(self.mint.key())];
+
+        let instruction = Instruction {
+            program_id: self.token_program,
+            accounts: &account_metas,
+            data: &[21],
+        };
+
+        invoke_signed(&instruction, &[self.mint], &[])?;
+
+        let return_data = get_return_data().ok_or(ProgramError::InvalidAccountData)?;
+        if return_data.program_id() != self.token_program || return_data.len() != 8 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut len = [0u8; 8];
+        len.copy_from_slice(&return_data);
+        Ok(u64::from_le_bytes(len) as usize)
+    }
+}
+
+/// Transfer tokens, checking the amount against the mint decimals.
+///
+/// Any `extra_accounts` are appended as-is, which is how transfer-hook mints
+/// receive the accounts their hook program needs.
+///
+/// ### Accounts:
+///   0. `[WRITE]` Source account
+///   1. `[]` Token mint
+///   2. `[WRITE]` Destination account
+///   3. `[SIGNER]` Source account authority
+///   4. ..`[]` Extra accounts forwarded to the transfer hook
+pub struct TransferChecked<'a> {
+    /// Source token account.
+    pub from: &'a AccountInfo,
+
+    /// Mint account.
+    pub mint: &'a AccountInfo,
+
+    /// Destination token account.
+    pub to: &'a AccountInfo,
+
+    /// Authority of the source account.
+    pub authority: &'a AccountInfo,
+
+    /// Extra accounts required by the mint's transfer hook.
+    pub extra_accounts: &'a [AccountInfo],
+
+    /// Amount of tokens to transfer.
+    pub amount: u64,
+
+    /// Decimals of the mint.
+    pub decimals: u8,
+
+    /// Token program owning the mint.
+    pub token_program: &'a Pubkey,
+}
+
+impl TransferChecked<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.extra_accounts.len() > MAX_TRANSFER_HOOK_ACCOUNTS {
+            return Err(MyProgramError::TooManyAccounts.into());
+        }
+
+        let len = 4 + self.extra_accounts.len();
+
+        // Unused slots are filled with the mint and never passed to the CPI.
+        let mut account_metas: [AccountMeta; 4 + MAX_TRANSFER_HOOK_ACCOUNTS] =
+            core::array::from_fn(|_| AccountMeta::readonly(self.mint.key()));
+        let mut account_infos: [&AccountInfo; 4 + MAX_TRANSFER_HOOK_ACCOUNTS] =
+            [self.mint; 4 + MAX_TRANSFER_HOOK_ACCOUNTS];
+
+        account_metas[0] = AccountMeta::writable(self.from.key());
+        account_metas[2] = AccountMeta::writable(self.to.key());
+        account_metas[3] = AccountMeta::readonly_signer(self.authority.key());
+        account_infos[0] = self.from;
+        account_infos[2] = self.to;
+        account_infos[3] = self.authority;
+
+        for (i, account) in self.extra_accounts.iter().enumerate() {
+            account_metas[4 + i] =
+                AccountMeta::new(account.key(), account.is_writable(), account.is_signer());
+            account_infos[4 + i] = account;
+        }
+
+        // instruction data
+        // -  [0]   : instruction discriminator
+        // -  [1..9]: amount
+        // -  [9]   : decimals
+        let mut instruction_data = [0; 10];
+        instruction_data[0] = 12;
+        instruction_data[1..9].copy_from_slice(&self.amount.to_le_bytes());
+        instruction_data[9] = self.decimals;
+
+        let instruction = Instruction {
+            program_id: self.token_program,
+            accounts: &account_metas[..len],
+            data: &instruction_data,
+        };
+
+        slice_invoke_signed(&instruction, &account_infos[..len], signers)
+    }
+}
+
+/// Move withheld transfer fees from token accounts into their Token-2022 mint.
+/// Permissionless.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint
+///   1. `[WRITE]` The source account
+pub struct HarvestWithheldTokensToMint<'a> {
+    /// Mint account.
+    pub mint: &'a AccountInfo,
+
+    /// Token account holding withheld fees.
+    pub source: &'a AccountInfo,
+}
+
+impl HarvestWithheldTokensToMint<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        let account_metas: [AccountMeta; 2] = [
+            AccountMeta::writable(self.mint.key()),
+            AccountMeta::writable(self.source.key()),
+        ];
+
+        // instruction data
+        // -  [0]: transfer fee extension discriminator
+        // -  [1]: harvest withheld tokens to mint
+        let instruction = Instruction {
+            program_id: &TOKEN_2022_PROGRAM_ID,
+            accounts: &account_metas,
+            data: &[26, 4],
+        };
+
+        invoke_signed(&instruction, &[self.mint, self.source], &[])
+    }
+}
+
+/// Close a token account with a zero balance, returning its lamports.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The account to close
+///   1. `[WRITE]` Destination of the lamports
+///   2. `[SIGNER]` Account close authority
+pub struct CloseAccount<'a> {
+    /// Token account to close.
+    pub account: &'a AccountInfo,
+
+    /// Destination of the remaining lamports.
+    pub destination: &'a AccountInfo,
+
+    /// Close authority of the token account.
+    pub authority: &'a AccountInfo,
+
+    /// Token program owning the account.
+    pub token_program: &'a Pubkey,
+}
+
+impl CloseAccount<'_> {
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let account_metas: [AccountMeta; 3] = [
+            AccountMeta::writable(self.account.key()),
+            AccountMeta::writable(self.destination.key()),
+            AccountMeta::readonly_signer(self.authority.key()),
+        ];
+
+        let instruction = Instruction {
+            program_id: self.token_program,
+            accounts: &account_metas,
+            data: &[9],
+        };
+
+        invoke_signed(
+            &instruction,
+            &[self.account, self.destination, self.authority],
+            signers,
+        )
+    }
+}
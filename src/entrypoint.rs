@@ -1,9 +1,11 @@
 #![allow(unexpected_cfgs)]
 
+use crate::errors::MyProgramError;
+use crate::events::split_event_accounts;
 use crate::instructions::{self, ProgramInstruction};
 use pinocchio::{
     account_info::AccountInfo, default_panic_handler, no_allocator, program_entrypoint,
-    program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+    pubkey::Pubkey, ProgramResult,
 };
 
 // This is the entrypoint for the program.
@@ -21,14 +23,99 @@ fn process_instruction(
 ) -> ProgramResult {
     let (ix_disc, instruction_data) = instruction_data
         .split_first()
-        .ok_or(ProgramError::InvalidInstructionData)?;
+        .ok_or(MyProgramError::InvalidInstructionData)?;
+
+    // Trailing `[event_authority, program]` accounts opt into self-CPI events.
+    let (accounts, event_authority) = split_event_accounts(accounts);
 
     match ProgramInstruction::try_from(ix_disc)? {
-        ProgramInstruction::Init => instructions::init_vault(accounts, instruction_data),
-        ProgramInstruction::Deposit => instructions::deposit_to_vault(accounts, instruction_data),
+        ProgramInstruction::Init => {
+            instructions::init_vault(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::Deposit => {
+            instructions::deposit_to_vault(accounts, instruction_data, event_authority)
+        }
         ProgramInstruction::Withdraw => {
-            instructions::withdraw_from_vault(accounts, instruction_data)
+            instructions::withdraw_from_vault(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::Close => {
+            instructions::close_vault(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::InitTokenVault => {
+            instructions::init_token_vault(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::DepositToken => {
+            instructions::deposit_token_to_vault(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::WithdrawToken => {
+            instructions::withdraw_token_from_vault(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::CloseTokenVault => {
+            instructions::close_token_vault(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::SetLock => {
+            instructions::set_vault_lock(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::RequestWithdraw => {
+            instructions::request_withdraw(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::ExecuteWithdraw => {
+            instructions::execute_withdraw(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::CancelWithdraw => {
+            instructions::cancel_withdraw(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::SetCooldown => {
+            instructions::set_withdraw_cooldown(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::SetWithdrawLimit => {
+            instructions::set_withdraw_limit(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::Approve => {
+            instructions::approve_delegate(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::Revoke => {
+            instructions::revoke_delegate(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::DelegateWithdraw => {
+            instructions::delegate_withdraw(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::InitMultisig => {
+            instructions::init_multisig(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::MultisigWithdraw => {
+            instructions::multisig_withdraw(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::MultisigClose => {
+            instructions::multisig_close(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::ProposeOwner => {
+            instructions::propose_owner(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::AcceptOwner => {
+            instructions::accept_owner(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::WithdrawAll => {
+            instructions::withdraw_all_from_vault(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::SetDepositPolicy => {
+            instructions::set_deposit_policy(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::InitWithDeposit => {
+            instructions::init_vault_with_deposit(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::InitIdempotent => {
+            instructions::init_vault_idempotent(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::EmitEvent => instructions::emit_event(accounts, instruction_data),
+        ProgramInstruction::InitV2 => {
+            instructions::init_vault_v2(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::WithdrawV2 => {
+            instructions::withdraw_from_vault_v2(accounts, instruction_data, event_authority)
+        }
+        ProgramInstruction::CloseV0 => {
+            instructions::close_vault_v0(accounts, instruction_data, event_authority)
         }
-        ProgramInstruction::Close => instructions::close_vault(accounts, instruction_data),
     }
 }
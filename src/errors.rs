@@ -1,14 +1,115 @@
 use pinocchio::program_error::ProgramError;
 
-#[derive(Clone, PartialEq, shank::ShankType)]
+/// Errors returned as `ProgramError::Custom(code)`.
+///
+/// Codes are pinned and part of the client interface: never renumber or reuse
+/// one, only append new variants.
+#[derive(Clone, Copy, Debug, PartialEq, shank::ShankType)]
+#[repr(u32)]
 pub enum MyProgramError {
-    InvalidInstructionData,
-    PdaMismatch,
-    InvalidOwner,
+    InvalidInstructionData = 0,
+    PdaMismatch = 1,
+    InvalidOwner = 2,
+    InvalidDiscriminator = 3,
+    UnsupportedVersion = 4,
+    InvalidProgramOwner = 5,
+    InvalidAccountLength = 6,
+    AccountNotWritable = 7,
+    InvalidTokenProgram = 8,
+    InvalidTokenAccount = 9,
+    InvalidMint = 10,
+    VaultLocked = 11,
+    LockShortened = 12,
+    WithdrawRequestRequired = 13,
+    WithdrawalAlreadyPending = 14,
+    NoPendingWithdrawal = 15,
+    CooldownActive = 16,
+    CooldownShortened = 17,
+    WithdrawLimitExceeded = 18,
+    WithdrawLimitLoosened = 19,
+    InvalidDelegate = 20,
+    InvalidDestination = 21,
+    DelegationExpired = 22,
+    AllowanceExceeded = 23,
+    InvalidThreshold = 24,
+    DuplicateSigner = 25,
+    ThresholdNotMet = 26,
+    InvalidPendingOwner = 27,
+    InvalidEventAuthority = 28,
+    ZeroAmount = 29,
+    InsufficientVaultBalance = 30,
+    BelowRentMinimum = 31,
+    VaultNotInitialized = 32,
+    VaultAlreadyInitialized = 33,
+    WrongSystemProgram = 34,
+    InvalidRentSysvar = 35,
+    TooManyAccounts = 36,
+    UnexpectedTokenBalance = 37,
 }
 
 impl From<MyProgramError> for ProgramError {
     fn from(e: MyProgramError) -> Self {
         Self::Custom(e as u32)
     }
-}       
+}
+
+#[cfg(feature = "std")]
+impl MyProgramError {
+    /// Human-readable description for clients and logs.
+    pub fn message(&self) -> &'static str {
+        match self {
+            MyProgramError::InvalidInstructionData => "invalid instruction data",
+            MyProgramError::PdaMismatch => "account is not at the expected PDA",
+            MyProgramError::InvalidOwner => "signer is not the vault owner",
+            MyProgramError::InvalidDiscriminator => "account has the wrong discriminator",
+            MyProgramError::UnsupportedVersion => "account layout version is not supported",
+            MyProgramError::InvalidProgramOwner => "account is not owned by the vault program",
+            MyProgramError::InvalidAccountLength => "account data has the wrong length",
+            MyProgramError::AccountNotWritable => "account must be writable",
+            MyProgramError::InvalidTokenProgram => "not the SPL Token or Token-2022 program",
+            MyProgramError::InvalidTokenAccount => "token account does not match the vault",
+            MyProgramError::InvalidMint => "mint is not owned by the token program",
+            MyProgramError::VaultLocked => "vault is time locked",
+            MyProgramError::LockShortened => "unlock timestamp can only be extended",
+            MyProgramError::WithdrawRequestRequired => {
+                "vault has a cooldown, request the withdrawal first"
+            }
+            MyProgramError::WithdrawalAlreadyPending => "a withdrawal is already pending",
+            MyProgramError::NoPendingWithdrawal => "no withdrawal is pending",
+            MyProgramError::CooldownActive => "withdrawal cooldown has not elapsed",
+            MyProgramError::CooldownShortened => "cooldown can only be lengthened",
+            MyProgramError::WithdrawLimitExceeded => "withdrawal exceeds the period limit",
+            MyProgramError::WithdrawLimitLoosened => "withdrawal limit can only be tightened",
+            MyProgramError::InvalidDelegate => "signer is not the vault delegate",
+            MyProgramError::InvalidDestination => "destination is not allowed",
+            MyProgramError::DelegationExpired => "delegation has expired",
+            MyProgramError::AllowanceExceeded => "withdrawal exceeds the delegate allowance",
+            MyProgramError::InvalidThreshold => "threshold must be between 1 and the signer count",
+            MyProgramError::DuplicateSigner => "multisig members must be distinct",
+            MyProgramError::ThresholdNotMet => "not enough multisig members signed",
+            MyProgramError::InvalidPendingOwner => "signer is not the proposed owner",
+            MyProgramError::InvalidEventAuthority => "not the event authority PDA",
+            MyProgramError::ZeroAmount => "amount must be greater than zero",
+            MyProgramError::InsufficientVaultBalance => "vault balance is lower than the amount",
+            MyProgramError::BelowRentMinimum => "vault would drop below the rent-exempt minimum",
+            MyProgramError::VaultNotInitialized => "vault is not initialized",
+            MyProgramError::VaultAlreadyInitialized => "vault is already initialized",
+            MyProgramError::WrongSystemProgram => "account is not the system program",
+            MyProgramError::InvalidRentSysvar => "account is not the rent sysvar",
+            MyProgramError::TooManyAccounts => "more accounts than the instruction takes",
+            MyProgramError::UnexpectedTokenBalance => {
+                "vault token balance decreased during the transfer"
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Display for MyProgramError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MyProgramError {}
@@ -0,0 +1,226 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Seed, Signer},
+    program::invoke_signed,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+use pinocchio_log::logger::Logger;
+
+use crate::instructions::ProgramInstruction;
+
+/// Size of the buffer an event line is formatted into.
+pub const EVENT_LOG_LEN: usize = 256;
+
+/// Prefix of every event line, so indexers can pick events out of the log stream.
+pub const EVENT_LOG_PREFIX: &str = "vault_event";
+
+/// Seed of the PDA that signs `EmitEvent` self-invocations.
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// `find_program_address(&[EVENT_AUTHORITY_SEED], &ID)`.
+pub const EVENT_AUTHORITY: Pubkey =
+    pinocchio_pubkey::from_str("2rMGiSWkSuN5vneAWF4yZrfonNmDx3k81zrY1DgpNEff");
+
+/// Canonical bump of [`EVENT_AUTHORITY`].
+pub const EVENT_AUTHORITY_BUMP: u8 = 255;
+
+/// Length of a serialized event: instruction, vault, owner, amount, balance.
+pub const EVENT_LEN: usize = 1 + 32 + 32 + 8 + 8;
+
+/// Effect of one instruction on a vault, logged for off-chain indexers.
+///
+/// Every instruction logs exactly one line on success, except an `InitIdempotent`
+/// retry on an existing vault, which changes nothing and logs none:
+///
+/// ```text
+/// vault_event ix=<name> vault=<hex> owner=<hex> amount=<u64> balance=<u64>
+/// ```
+///
+/// Keys are lowercase hex. `amount` is what the instruction moved, 0 for
+/// configuration changes. `balance` is what the vault holds afterwards: lamports
+/// for SOL and multisig vaults, token units for token vaults. For multisig vaults
+/// `owner` is the creator.
+pub struct VaultEvent<'a> {
+    pub instruction: ProgramInstruction,
+    pub vault: &'a Pubkey,
+    pub owner: &'a Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+}
+
+impl VaultEvent<'_> {
+    /// Formats the event line without logging it.
+    pub fn to_logger(&self) -> Logger<EVENT_LOG_LEN> {
+        let mut logger = Logger::default();
+        logger
+            .append(EVENT_LOG_PREFIX)
+            .append(" ix=")
+            .append(self.instruction.name())
+            .append(" vault=");
+        append_hex(&mut logger, self.vault);
+        logger.append(" owner=");
+        append_hex(&mut logger, self.owner);
+        logger
+            .append(" amount=")
+            .append(self.amount)
+            .append(" balance=")
+            .append(self.balance);
+        logger
+    }
+
+    pub fn log(&self) {
+        self.to_logger().log();
+    }
+
+    /// Serializes the event as carried by `EmitEvent`; integers are little-endian.
+    pub fn to_bytes(&self) -> [u8; EVENT_LEN] {
+        let mut bytes = [0u8; EVENT_LEN];
+        bytes[0] = self.instruction as u8;
+        bytes[1..33].copy_from_slice(self.vault);
+        bytes[33..65].copy_from_slice(self.owner);
+        bytes[65..73].copy_from_slice(&self.amount.to_le_bytes());
+        bytes[73..81].copy_from_slice(&self.balance.to_le_bytes());
+        bytes
+    }
+
+    /// Logs the event and, if the caller passed the event accounts, also records it
+    /// as an `EmitEvent` inner instruction, which survives log truncation.
+    pub fn emit(&self, event_authority: Option<&AccountInfo>) -> ProgramResult {
+        self.log();
+
+        let Some(event_authority) = event_authority else {
+            return Ok(());
+        };
+
+        let mut data = [0u8; 1 + EVENT_LEN];
+        data[0] = ProgramInstruction::EmitEvent as u8;
+        data[1..].copy_from_slice(&self.to_bytes());
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &[AccountMeta::readonly_signer(event_authority.key())],
+            data: &data,
+        };
+
+        let bump = [EVENT_AUTHORITY_BUMP];
+        let seeds = [Seed::from(EVENT_AUTHORITY_SEED), Seed::from(&bump[..])];
+
+        invoke_signed(
+            &instruction,
+            &[event_authority],
+            &[Signer::from(&seeds[..])],
+        )
+    }
+}
+
+/// Splits off the optional trailing `[event_authority, program]` accounts.
+///
+/// Any instruction accepts them; without them events are only logged.
+pub fn split_event_accounts(accounts: &[AccountInfo]) -> (&[AccountInfo], Option<&AccountInfo>) {
+    match accounts {
+        [rest @ .., event_authority, program]
+            if event_authority.key() == &EVENT_AUTHORITY && program.key() == &crate::ID =>
+        {
+            (rest, Some(event_authority))
+        }
+        _ => (accounts, None),
+    }
+}
+
+/// Hex keeps keys cheap to format on chain, unlike base58 or `log!`'s byte-array output.
+fn append_hex(logger: &mut Logger<EVENT_LOG_LEN>, key: &Pubkey) {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    let mut hex = [0u8; 64];
+    for (i, byte) in key.iter().enumerate() {
+        hex[2 * i] = DIGITS[(byte >> 4) as usize];
+        hex[2 * i + 1] = DIGITS[(byte & 0x0f) as usize];
+    }
+
+    // SAFETY: `hex` only contains ASCII digits.
+    logger.append(unsafe { core::str::from_utf8_unchecked(&hex) });
+}
+
+#[cfg(feature = "std")]
+pub use decoder::*;
+
+#[cfg(feature = "std")]
+mod decoder {
+    use core::fmt;
+
+    use pinocchio::pubkey::Pubkey;
+
+    use super::EVENT_LEN;
+    use crate::instructions::ProgramInstruction;
+
+    /// Owned copy of a [`VaultEvent`](super::VaultEvent) read back from an inner instruction.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct DecodedEvent {
+        pub instruction: ProgramInstruction,
+        pub vault: Pubkey,
+        pub owner: Pubkey,
+        pub amount: u64,
+        pub balance: u64,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum EventDecodeError {
+        /// The instruction is not `EmitEvent`.
+        NotAnEvent,
+        /// The payload is not `EVENT_LEN` bytes.
+        InvalidLength(usize),
+        /// The payload names an instruction this version doesn't know.
+        UnknownInstruction(u8),
+    }
+
+    impl fmt::Display for EventDecodeError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                EventDecodeError::NotAnEvent => write!(f, "instruction is not EmitEvent"),
+                EventDecodeError::InvalidLength(len) => {
+                    write!(f, "event payload is {len} bytes, expected {EVENT_LEN}")
+                }
+                EventDecodeError::UnknownInstruction(ix) => {
+                    write!(f, "event names unknown instruction {ix}")
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for EventDecodeError {}
+
+    impl DecodedEvent {
+        /// Decodes the data of an inner instruction to this program.
+        ///
+        /// Callers should only pass inner instructions whose program id is this
+        /// program's; only the event authority can sign a valid `EmitEvent`.
+        pub fn decode(instruction_data: &[u8]) -> Result<Self, EventDecodeError> {
+            let (disc, payload) = instruction_data
+                .split_first()
+                .ok_or(EventDecodeError::NotAnEvent)?;
+            if *disc != ProgramInstruction::EmitEvent as u8 {
+                return Err(EventDecodeError::NotAnEvent);
+            }
+            if payload.len() != EVENT_LEN {
+                return Err(EventDecodeError::InvalidLength(payload.len()));
+            }
+
+            let instruction = ProgramInstruction::try_from(&payload[0])
+                .map_err(|_| EventDecodeError::UnknownInstruction(payload[0]))?;
+            let u64_at = |offset: usize| {
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(&payload[offset..offset + 8]);
+                u64::from_le_bytes(bytes)
+            };
+
+            Ok(DecodedEvent {
+                instruction,
+                vault: payload[1..33].try_into().unwrap(),
+                owner: payload[33..65].try_into().unwrap(),
+                amount: u64_at(65),
+                balance: u64_at(73),
+            })
+        }
+    }
+}
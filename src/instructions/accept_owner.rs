@@ -0,0 +1,46 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::errors::MyProgramError;
+use crate::instructions::ProgramInstruction;
+use crate::states::{load_ix_data, DataLen, VaultAccount};
+
+#[repr(C)]
+pub struct AcceptOwner;
+
+impl DataLen for AcceptOwner {
+    const LEN: usize = core::mem::size_of::<AcceptOwner>();
+}
+
+/// Makes the pending owner the vault owner. Any delegation is dropped so the
+/// new owner never inherits an allowance they didn't approve.
+pub fn accept_owner(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [new_owner, vault] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !new_owner.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    unsafe { load_ix_data::<AcceptOwner>(data)? };
+
+    let mut vault_account = VaultAccount::load(vault)?;
+
+    let pending_owner = vault_account.state().pending_owner;
+    if pending_owner == [0; 32] || pending_owner != *new_owner.key() {
+        return Err(MyProgramError::InvalidPendingOwner.into());
+    }
+
+    let vault_state = vault_account.state_mut();
+    vault_state.owner = pending_owner;
+    vault_state.pending_owner = [0; 32];
+    vault_state.clear_delegate();
+
+    vault_account.emit_event(ProgramInstruction::AcceptOwner, 0, event_authority)?;
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::errors::MyProgramError;
+use crate::instructions::ProgramInstruction;
+use crate::states::{load_ix_data, DataLen, VaultAccount};
+
+#[repr(C)]
+pub struct Approve {
+    pub allowance: u64,
+    pub expiry: i64,
+}
+
+impl DataLen for Approve {
+    const LEN: usize = core::mem::size_of::<Approve>();
+}
+
+/// Lets `delegate` withdraw up to `allowance` lamports to `destination`,
+/// replacing any previous delegation.
+pub fn approve_delegate(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault, delegate, destination] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !user.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    let approve_ix_data = unsafe { load_ix_data::<Approve>(data)? };
+
+    if approve_ix_data.allowance.eq(&0) {
+        return Err(MyProgramError::ZeroAmount.into());
+    };
+
+    // Funds sent to the vault itself would count against the allowance without
+    // leaving it, and the zero key is not an account anyone controls.
+    if destination.key() == vault.key() || *destination.key() == [0; 32] {
+        return Err(MyProgramError::InvalidDestination.into());
+    }
+
+    let mut vault_account = VaultAccount::load(vault)?;
+    vault_account.check_owner(user)?;
+
+    let vault_state = vault_account.state_mut();
+    vault_state.delegate = *delegate.key();
+    vault_state.delegate_destination = *destination.key();
+    vault_state.delegate_allowance = approve_ix_data.allowance;
+    vault_state.delegate_expiry = approve_ix_data.expiry;
+
+    vault_account.emit_event(ProgramInstruction::Approve, 0, event_authority)?;
+
+    Ok(())
+}
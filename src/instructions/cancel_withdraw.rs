@@ -0,0 +1,44 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::errors::MyProgramError;
+use crate::instructions::ProgramInstruction;
+use crate::states::{load_ix_data, DataLen, VaultAccount};
+
+#[repr(C)]
+pub struct CancelWithdraw;
+
+impl DataLen for CancelWithdraw {
+    const LEN: usize = core::mem::size_of::<CancelWithdraw>();
+}
+
+pub fn cancel_withdraw(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !user.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    unsafe { load_ix_data::<CancelWithdraw>(data)? };
+
+    let mut vault_account = VaultAccount::load(vault)?;
+    vault_account.check_owner(user)?;
+
+    let vault_state = vault_account.state_mut();
+
+    if vault_state.pending_withdraw_amount == 0 {
+        return Err(MyProgramError::NoPendingWithdrawal.into());
+    }
+
+    vault_state.pending_withdraw_amount = 0;
+    vault_state.pending_withdraw_requested_at = 0;
+
+    vault_account.emit_event(ProgramInstruction::CancelWithdraw, 0, event_authority)?;
+
+    Ok(())
+}
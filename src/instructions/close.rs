@@ -1,45 +1,83 @@
-use crate::states::{load_acc_unchecked, load_ix_data, DataLen, VaultState};
-use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use crate::cpi::system::check_system_program;
+use crate::errors::MyProgramError;
+use crate::events::VaultEvent;
+use crate::instructions::ProgramInstruction;
+use crate::states::{load_ix_data, DataLen, VaultAccount, VaultState};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
 
 #[repr(C)]
-pub struct Close {
-    pub bump: u8,
-}
+pub struct Close;
 
 impl DataLen for Close {
     const LEN: usize = core::mem::size_of::<Close>();
 }
 
-pub fn close_vault(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
-    let [user, vault, _] = accounts else {
+/// Closes the vault into the optional trailing `destination` account, or into
+/// `user` when none is passed.
+pub fn close_vault(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault, system_program, rest @ ..] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
+    let destination = match rest {
+        [] => user,
+        [destination] => destination,
+        _ => return Err(MyProgramError::TooManyAccounts.into()),
+    };
+
     if !user.is_signer() {
         return Err(ProgramError::MissingRequiredSignature);
     };
 
-    let close_ix_data = unsafe { load_ix_data::<Close>(data)? };
+    check_system_program(system_program)?;
+
+    unsafe { load_ix_data::<Close>(data)? };
+
+    let mut vault_account = VaultAccount::load(vault)?;
+    vault_account.check_owner(user)?;
+    vault_account.check_unlocked()?;
 
-    VaultState::validate_pda(close_ix_data.bump, vault.key(), user.key())?;
+    let min_balance = Rent::get()?.minimum_balance(VaultState::LEN);
+    let withdrawable = vault.lamports().saturating_sub(min_balance);
 
-    let vault_state = unsafe { load_acc_unchecked::<VaultState>(vault.borrow_data_unchecked()) }?;
+    // With a cooldown set, funds leave through `ExecuteWithdraw`; closing only
+    // recovers the rent reserve.
+    if vault_account.state().requires_withdraw_request() && withdrawable > 0 {
+        return Err(MyProgramError::WithdrawRequestRequired.into());
+    }
+
+    // Everything above the rent reserve counts against the rate limit.
+    vault_account.record_withdrawal(withdrawable)?;
 
-    if vault_state.owner != *user.key() {
-        return Err(ProgramError::InvalidAccountOwner);
+    if !destination.is_writable() {
+        return Err(MyProgramError::AccountNotWritable.into());
+    }
+    if destination.key() == vault.key() {
+        return Err(MyProgramError::InvalidDestination.into());
     }
 
-    // Move all lamports directly; system transfer would reject `from` with data.
-    unsafe {
-        let vault_lamports = vault.borrow_mut_lamports_unchecked();
-        let user_lamports = user.borrow_mut_lamports_unchecked();
-        let amount = *vault_lamports;
-        *vault_lamports = 0;
-        *user_lamports = (*user_lamports)
-            .checked_add(amount)
-            .ok_or(ProgramError::InsufficientFunds)?;
+    let owner = vault_account.state().owner;
+    let amount = vault.lamports();
+
+    vault_account.close(destination)?;
+
+    VaultEvent {
+        instruction: ProgramInstruction::Close,
+        vault: vault.key(),
+        owner: &owner,
+        amount,
+        balance: 0,
     }
+    .emit(event_authority)?;
 
-    // After draining lamports, Solana runtime would garbage collect rent-ineligible accounts.
     Ok(())
 }
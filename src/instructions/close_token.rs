@@ -0,0 +1,110 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::cpi::token::{
+    is_token_program, withheld_amount, CloseAccount, HarvestWithheldTokensToMint, TransferChecked,
+    TOKEN_2022_PROGRAM_ID,
+};
+use crate::errors::MyProgramError;
+use crate::events::VaultEvent;
+use crate::instructions::ProgramInstruction;
+use crate::states::{load_ix_data, DataLen, TokenVaultAccount, VaultState};
+
+#[repr(C)]
+pub struct CloseTokenVault {
+    pub bump: u8,
+}
+
+impl DataLen for CloseTokenVault {
+    const LEN: usize = core::mem::size_of::<CloseTokenVault>();
+}
+
+pub fn close_token_vault(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault, user_token_account, mint, token_program, extra_accounts @ ..] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !user.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    if !is_token_program(token_program.key()) {
+        return Err(MyProgramError::InvalidTokenProgram.into());
+    }
+
+    let close_ix_data = unsafe { load_ix_data::<CloseTokenVault>(data)? };
+
+    let vault = TokenVaultAccount::load(
+        vault,
+        user.key(),
+        mint.key(),
+        token_program.key(),
+        close_ix_data.bump,
+    )?;
+    let decimals = TokenVaultAccount::mint_decimals(mint, token_program.key())?;
+
+    let bump_bytes = [close_ix_data.bump];
+
+    let signer_seeds = [
+        Seed::from(VaultState::SEED.as_bytes()),
+        Seed::from(user.key()),
+        Seed::from(mint.key()),
+        Seed::from(&bump_bytes[..]),
+    ];
+    let signer = [Signer::from(&signer_seeds[..])];
+
+    // Token-2022 refuses to close accounts holding withheld transfer fees.
+    if token_program.key() == &TOKEN_2022_PROGRAM_ID
+        && withheld_amount(unsafe { vault.info().borrow_data_unchecked() }) > 0
+    {
+        HarvestWithheldTokensToMint {
+            mint,
+            source: vault.info(),
+        }
+        .invoke()?;
+    }
+
+    // The token program only closes empty accounts, so sweep the balance first.
+    let amount = vault.amount();
+    if amount > 0 {
+        TransferChecked {
+            from: vault.info(),
+            mint,
+            to: user_token_account,
+            authority: vault.info(),
+            extra_accounts,
+            amount,
+            decimals,
+            token_program: token_program.key(),
+        }
+        .invoke_signed(&signer)?;
+    }
+
+    CloseAccount {
+        account: vault.info(),
+        destination: user,
+        authority: vault.info(),
+        token_program: token_program.key(),
+    }
+    .invoke_signed(&signer)?;
+
+    VaultEvent {
+        instruction: ProgramInstruction::CloseTokenVault,
+        vault: vault.info().key(),
+        owner: user.key(),
+        amount,
+        balance: 0,
+    }
+    .emit(event_authority)?;
+
+    Ok(())
+}
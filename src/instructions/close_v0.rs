@@ -0,0 +1,86 @@
+use crate::errors::MyProgramError;
+use crate::events::VaultEvent;
+use crate::instructions::ProgramInstruction;
+use crate::states::{close_program_account, load_ix_data, DataLen, VaultState};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+    ProgramResult,
+};
+
+#[repr(C)]
+pub struct CloseV0 {
+    pub bump: u8,
+}
+
+impl DataLen for CloseV0 {
+    const LEN: usize = core::mem::size_of::<CloseV0>();
+}
+
+/// Closes a vault created by the first deployment of the program into `user`.
+///
+/// Those vaults hold only the 32-byte owner, without a discriminator or version,
+/// and live at `[SEED, owner, bump]` with a caller-supplied bump, so none of the
+/// current loaders accept them. `user` must be the stored owner and sign.
+pub fn close_vault_v0(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !user.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    let close_ix_data = unsafe { load_ix_data::<CloseV0>(data)? };
+
+    if !vault.is_owned_by(&crate::ID) {
+        return Err(if vault.data_is_empty() {
+            MyProgramError::VaultNotInitialized.into()
+        } else {
+            MyProgramError::InvalidProgramOwner.into()
+        });
+    }
+    if !vault.is_writable() {
+        return Err(MyProgramError::AccountNotWritable.into());
+    }
+    if vault.data_len() != VaultState::V0_LEN {
+        return Err(MyProgramError::InvalidAccountLength.into());
+    }
+
+    let seed_with_bump = &[
+        VaultState::SEED.as_bytes(),
+        user.key().as_ref(),
+        &[close_ix_data.bump],
+    ];
+    let derived = pubkey::create_program_address(seed_with_bump, &crate::ID)?;
+    if derived != *vault.key() {
+        return Err(MyProgramError::PdaMismatch.into());
+    }
+
+    let owner: Pubkey = vault.try_borrow_data()?[..VaultState::V0_LEN]
+        .try_into()
+        .map_err(|_| MyProgramError::InvalidAccountLength)?;
+    if owner != *user.key() {
+        return Err(MyProgramError::InvalidOwner.into());
+    }
+
+    let amount = vault.lamports();
+
+    close_program_account(vault, user)?;
+
+    VaultEvent {
+        instruction: ProgramInstruction::CloseV0,
+        vault: vault.key(),
+        owner: &owner,
+        amount,
+        balance: 0,
+    }
+    .emit(event_authority)?;
+
+    Ok(())
+}
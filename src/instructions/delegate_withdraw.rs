@@ -0,0 +1,79 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::cpi::system::check_system_program;
+use crate::errors::MyProgramError;
+use crate::instructions::{ProgramInstruction, Withdraw};
+use crate::states::{load_ix_data, load_rent, VaultAccount};
+
+/// Withdraws on behalf of the owner. The delegate signs, the allowance is
+/// decremented and funds can only go to the destination the owner approved.
+/// Takes the same data as `Withdraw`.
+pub fn delegate_withdraw(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [delegate, vault, destination, rent_sysvar, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !delegate.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    check_system_program(system_program)?;
+
+    let withdraw_ix_data = unsafe { load_ix_data::<Withdraw>(data)? };
+
+    if withdraw_ix_data.amount.eq(&0) {
+        return Err(MyProgramError::ZeroAmount.into());
+    };
+
+    let mut vault_account = VaultAccount::load(vault)?;
+    vault_account.check_unlocked()?;
+
+    let vault_state = vault_account.state();
+
+    if vault_state.delegate == [0; 32] || vault_state.delegate != *delegate.key() {
+        return Err(MyProgramError::InvalidDelegate.into());
+    }
+
+    if vault_state.delegate_destination != *destination.key() {
+        return Err(MyProgramError::InvalidDestination.into());
+    }
+
+    if vault_state.delegation_expired(Clock::get()?.unix_timestamp) {
+        return Err(MyProgramError::DelegationExpired.into());
+    }
+
+    // A delegate must not become a way around the owner's cooldown.
+    if vault_state.requires_withdraw_request() {
+        return Err(MyProgramError::WithdrawRequestRequired.into());
+    }
+
+    let allowance = vault_state
+        .delegate_allowance
+        .checked_sub(withdraw_ix_data.amount)
+        .ok_or(MyProgramError::AllowanceExceeded)?;
+
+    vault_account.record_withdrawal(withdraw_ix_data.amount)?;
+
+    let rent = load_rent(rent_sysvar)?;
+
+    vault_account.transfer_lamports(destination, withdraw_ix_data.amount, &rent)?;
+
+    vault_account.state_mut().delegate_allowance = allowance;
+
+    vault_account.emit_event(
+        ProgramInstruction::DelegateWithdraw,
+        withdraw_ix_data.amount,
+        event_authority,
+    )?;
+
+    Ok(())
+}
@@ -2,20 +2,26 @@ use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramR
 
 use pinocchio_system::instructions::Transfer;
 
-use crate::states::{load_acc_unchecked, load_ix_data, DataLen, VaultState};
+use crate::cpi::system::check_system_program;
+use crate::errors::MyProgramError;
+use crate::instructions::ProgramInstruction;
+use crate::states::{load_ix_data, DataLen, VaultAccount};
 
 #[repr(C)]
 pub struct Deposit {
     pub amount: u64,
-    pub bump: u8,
 }
 
 impl DataLen for Deposit {
     const LEN: usize = core::mem::size_of::<Deposit>();
 }
 
-pub fn deposit_to_vault(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
-    let [user, vault, _] = accounts else {
+pub fn deposit_to_vault(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault, system_program] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
@@ -23,22 +29,18 @@ pub fn deposit_to_vault(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
         return Err(ProgramError::MissingRequiredSignature);
     }
 
+    check_system_program(system_program)?;
+
     //ix data parsing
     let deposit_data = unsafe { load_ix_data::<Deposit>(data)? };
 
     if deposit_data.amount.eq(&0) {
-        return Err(ProgramError::InvalidInstructionData);
+        return Err(MyProgramError::ZeroAmount.into());
     };
 
-    //validating pda
-    VaultState::validate_pda(deposit_data.bump, vault.key(), user.key())?;
-
-    //reading state for auth
-    let vault_state = unsafe { load_acc_unchecked::<VaultState>(vault.borrow_data_unchecked()) }?;
-
-    if vault_state.owner != *user.key() {
-        return Err(ProgramError::InvalidAccountOwner);
-    }
+    //validating vault account and, if the vault requires it, the owner
+    let vault_account = VaultAccount::load(vault)?;
+    vault_account.check_depositor(user)?;
 
     //actual transfer
     Transfer {
@@ -48,5 +50,11 @@ pub fn deposit_to_vault(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
     }
     .invoke()?;
 
+    vault_account.emit_event(
+        ProgramInstruction::Deposit,
+        deposit_data.amount,
+        event_authority,
+    )?;
+
     Ok(())
 }
@@ -0,0 +1,86 @@
+use pinocchio::{
+    account_info::AccountInfo, program::set_return_data, program_error::ProgramError, ProgramResult,
+};
+
+use crate::cpi::token::{is_token_program, TransferChecked};
+use crate::errors::MyProgramError;
+use crate::instructions::ProgramInstruction;
+use crate::states::{load_ix_data, DataLen, TokenVaultAccount};
+
+#[repr(C)]
+pub struct DepositToken {
+    pub amount: u64,
+    pub bump: u8,
+}
+
+impl DataLen for DepositToken {
+    const LEN: usize = core::mem::size_of::<DepositToken>();
+}
+
+/// Deposits tokens and sets the amount actually credited to the vault as return data.
+///
+/// For Token-2022 mints with a transfer fee the credited amount is lower than
+/// `amount`; it is measured from the vault balance rather than recomputed.
+pub fn deposit_token_to_vault(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, user_token_account, vault, mint, token_program, extra_accounts @ ..] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !user.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !is_token_program(token_program.key()) {
+        return Err(MyProgramError::InvalidTokenProgram.into());
+    }
+
+    let deposit_data = unsafe { load_ix_data::<DepositToken>(data)? };
+
+    if deposit_data.amount.eq(&0) {
+        return Err(MyProgramError::ZeroAmount.into());
+    };
+
+    let vault = TokenVaultAccount::load(
+        vault,
+        user.key(),
+        mint.key(),
+        token_program.key(),
+        deposit_data.bump,
+    )?;
+    let decimals = TokenVaultAccount::mint_decimals(mint, token_program.key())?;
+
+    let balance_before = vault.amount();
+
+    TransferChecked {
+        from: user_token_account,
+        mint,
+        to: vault.info(),
+        authority: user,
+        extra_accounts,
+        amount: deposit_data.amount,
+        decimals,
+        token_program: token_program.key(),
+    }
+    .invoke()?;
+
+    let received = vault
+        .amount()
+        .checked_sub(balance_before)
+        .ok_or(MyProgramError::UnexpectedTokenBalance)?;
+
+    vault.emit_event(
+        ProgramInstruction::DepositToken,
+        user.key(),
+        received,
+        event_authority,
+    )?;
+
+    set_return_data(&received.to_le_bytes());
+
+    Ok(())
+}
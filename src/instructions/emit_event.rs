@@ -0,0 +1,28 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::errors::MyProgramError;
+use crate::events::{EVENT_AUTHORITY, EVENT_LEN};
+
+/// Carries a serialized `VaultEvent` as an inner instruction for indexers.
+///
+/// Only reachable through the program's own CPI: the event authority is a PDA of
+/// this program, so a top-level call cannot sign for it.
+pub fn emit_event(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [event_authority] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if event_authority.key() != &EVENT_AUTHORITY {
+        return Err(MyProgramError::InvalidEventAuthority.into());
+    }
+
+    if !event_authority.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if data.len() != EVENT_LEN {
+        return Err(MyProgramError::InvalidInstructionData.into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::cpi::system::check_system_program;
+use crate::errors::MyProgramError;
+use crate::instructions::ProgramInstruction;
+use crate::states::{load_ix_data, load_rent, DataLen, VaultAccount};
+
+#[repr(C)]
+pub struct ExecuteWithdraw;
+
+impl DataLen for ExecuteWithdraw {
+    const LEN: usize = core::mem::size_of::<ExecuteWithdraw>();
+}
+
+pub fn execute_withdraw(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault, rent_sysvar, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !user.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    check_system_program(system_program)?;
+
+    unsafe { load_ix_data::<ExecuteWithdraw>(data)? };
+
+    let mut vault_account = VaultAccount::load(vault)?;
+    vault_account.check_owner(user)?;
+    vault_account.check_unlocked()?;
+
+    let vault_state = vault_account.state();
+    let amount = vault_state.pending_withdraw_amount;
+
+    if amount == 0 {
+        return Err(MyProgramError::NoPendingWithdrawal.into());
+    }
+
+    let executable_at = vault_state
+        .pending_withdraw_requested_at
+        .checked_add(vault_state.withdraw_cooldown)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    if Clock::get()?.unix_timestamp < executable_at {
+        return Err(MyProgramError::CooldownActive.into());
+    }
+
+    vault_account.record_withdrawal(amount)?;
+
+    let rent = load_rent(rent_sysvar)?;
+
+    vault_account.transfer_lamports(user, amount, &rent)?;
+
+    let vault_state = vault_account.state_mut();
+    vault_state.pending_withdraw_amount = 0;
+    vault_state.pending_withdraw_requested_at = 0;
+
+    vault_account.emit_event(ProgramInstruction::ExecuteWithdraw, amount, event_authority)?;
+
+    Ok(())
+}
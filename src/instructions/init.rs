@@ -1,3 +1,7 @@
+use crate::cpi::system::{check_system_program, create_pda_account};
+use crate::errors::MyProgramError;
+use crate::instructions::ProgramInstruction;
+use crate::states::{load_ix_data, load_rent, DataLen, VaultAccount, VaultState};
 use pinocchio::{
     account_info::AccountInfo,
     instruction::{Seed, Signer},
@@ -5,22 +9,26 @@ use pinocchio::{
     sysvars::rent::Rent,
     ProgramResult,
 };
-use pinocchio_system::instructions::CreateAccount;
-
-use crate::states::{load_ix_data, DataLen, VaultState};
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Init {
-    pub bump: u8,
+    pub unlock_timestamp: i64,
+    pub withdraw_cooldown: i64,
+    /// Name of the vault, e.g. `b"savings"` zero-padded; all zeros for the default vault.
+    pub label: [u8; 32],
 }
 
 impl DataLen for Init {
     const LEN: usize = core::mem::size_of::<Init>();
 }
 
-pub fn init_vault(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
-    let [user, vault_pda, rent, _] = accounts else {
+pub fn init_vault(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault_pda, rent, system_program] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
@@ -28,36 +36,87 @@ pub fn init_vault(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
         return Err(ProgramError::MissingRequiredSignature);
     };
 
+    check_system_program(system_program)?;
+
     let init_ix_data = unsafe { load_ix_data::<Init>(data)? };
 
-    if !vault_pda.data_is_empty() {
-        return Err(ProgramError::AccountAlreadyInitialized);
-    };
+    create_vault(
+        user,
+        vault_pda,
+        &load_rent(rent)?,
+        init_ix_data.unlock_timestamp,
+        init_ix_data.withdraw_cooldown,
+        &init_ix_data.label,
+        0,
+    )?
+    .emit_event(ProgramInstruction::Init, 0, event_authority)?;
 
-    VaultState::validate_pda(init_ix_data.bump, vault_pda.key(), user.key())?;
+    Ok(())
+}
+
+/// Creates and initializes `user`'s vault named `label`, funding it with the rent-exempt
+/// minimum plus `deposit` lamports in the same `CreateAccount` call.
+///
+/// Lamports already sitting at the address count towards the rent-exempt minimum
+/// only, so `user` always transfers the full `deposit`.
+pub(crate) fn create_vault<'a>(
+    user: &AccountInfo,
+    vault_pda: &'a AccountInfo,
+    rent: &Rent,
+    unlock_timestamp: i64,
+    withdraw_cooldown: i64,
+    label: &[u8; 32],
+    deposit: u64,
+) -> Result<VaultAccount<'a>, ProgramError> {
+    if withdraw_cooldown < 0 {
+        return Err(MyProgramError::InvalidInstructionData.into());
+    }
 
-    let rent = Rent::from_account_info(rent)?;
+    let bump = VaultAccount::check_uninitialized(vault_pda, user.key(), label)?;
 
-    let bump_bytes = [init_ix_data.bump];
+    let lamports = rent
+        .minimum_balance(VaultState::LEN)
+        .max(vault_pda.lamports())
+        .checked_add(deposit)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
 
-    let signer_seeds = [
+    let bump_bytes = [bump];
+
+    let default_seeds = [
         Seed::from(VaultState::SEED.as_bytes()),
         Seed::from(user.key()),
         Seed::from(&bump_bytes[..]),
     ];
+    let labeled_seeds = [
+        Seed::from(VaultState::LABELED_SEED.as_bytes()),
+        Seed::from(user.key()),
+        Seed::from(&label[..]),
+        Seed::from(&bump_bytes[..]),
+    ];
 
-    let signer = [Signer::from(&signer_seeds[..])];
+    let signer = [if *label == VaultState::DEFAULT_LABEL {
+        Signer::from(&default_seeds[..])
+    } else {
+        Signer::from(&labeled_seeds[..])
+    }];
 
-    CreateAccount {
-        from: user,
-        to: vault_pda,
-        space: VaultState::LEN as u64,
-        owner: &crate::ID,
-        lamports: rent.minimum_balance(VaultState::LEN),
-    }
-    .invoke_signed(&signer)?;
+    create_pda_account(
+        user,
+        vault_pda,
+        VaultState::LEN,
+        lamports,
+        &crate::ID,
+        &signer,
+    )?;
 
-    VaultState::initialize(vault_pda, user.key())?;
+    VaultState::initialize(
+        vault_pda,
+        user.key(),
+        label,
+        bump,
+        unlock_timestamp,
+        withdraw_cooldown,
+    )?;
 
-    Ok(())
+    VaultAccount::try_from(vault_pda)
 }
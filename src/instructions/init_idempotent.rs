@@ -0,0 +1,53 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::cpi::system::check_system_program;
+use crate::errors::MyProgramError;
+use crate::instructions::{init::create_vault, Init, ProgramInstruction};
+use crate::states::{load_ix_data, load_rent, VaultAccount};
+
+/// `Init` that succeeds without changes when the signer's vault already exists,
+/// so clients can retry it safely. Takes the same accounts and data as `Init`;
+/// for an existing vault only `label` is checked and no event is emitted.
+pub fn init_vault_idempotent(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault_pda, rent, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !user.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    check_system_program(system_program)?;
+
+    let init_ix_data = unsafe { load_ix_data::<Init>(data)? };
+
+    if vault_pda.is_owned_by(&crate::ID) {
+        // `load` ties the address to the stored creator and label, which must match
+        // the vault this call would have created.
+        let vault_account = VaultAccount::load(vault_pda)?;
+        let state = vault_account.state();
+        if state.creator != *user.key() || state.label != init_ix_data.label {
+            return Err(MyProgramError::PdaMismatch.into());
+        }
+        vault_account.check_owner(user)?;
+        // Nothing changed, so no event: indexers would record it as a second init.
+        return Ok(());
+    }
+
+    create_vault(
+        user,
+        vault_pda,
+        &load_rent(rent)?,
+        init_ix_data.unlock_timestamp,
+        init_ix_data.withdraw_cooldown,
+        &init_ix_data.label,
+        0,
+    )?
+    .emit_event(ProgramInstruction::InitIdempotent, 0, event_authority)?;
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use crate::cpi::system::{check_system_program, create_pda_account};
+use crate::instructions::ProgramInstruction;
+use crate::states::{load_ix_data, load_rent, DataLen, MultisigVaultAccount, MultisigVaultState};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+#[repr(C)]
+pub struct InitMultisig {
+    pub seed: [u8; 32],
+    pub threshold: u8,
+}
+
+impl DataLen for InitMultisig {
+    const LEN: usize = core::mem::size_of::<InitMultisig>();
+}
+
+/// Creates a multisig vault whose members are the trailing accounts.
+///
+/// Members don't need to sign here; their keys are only recorded.
+pub fn init_multisig(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [creator, vault_pda, rent, system_program, members @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !creator.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    check_system_program(system_program)?;
+
+    let init_ix_data = unsafe { load_ix_data::<InitMultisig>(data)? };
+
+    let bump =
+        MultisigVaultAccount::check_uninitialized(vault_pda, creator.key(), &init_ix_data.seed)?;
+
+    let rent = load_rent(rent)?;
+
+    let bump_bytes = [bump];
+
+    let signer_seeds = [
+        Seed::from(MultisigVaultState::SEED.as_bytes()),
+        Seed::from(creator.key()),
+        Seed::from(&init_ix_data.seed[..]),
+        Seed::from(&bump_bytes[..]),
+    ];
+
+    let signer = [Signer::from(&signer_seeds[..])];
+
+    create_pda_account(
+        creator,
+        vault_pda,
+        MultisigVaultState::LEN,
+        rent.minimum_balance(MultisigVaultState::LEN),
+        &crate::ID,
+        &signer,
+    )?;
+
+    MultisigVaultState::initialize(
+        vault_pda,
+        creator.key(),
+        &init_ix_data.seed,
+        bump,
+        init_ix_data.threshold,
+        members,
+    )?;
+
+    MultisigVaultAccount::try_from(vault_pda)?.emit_event(
+        ProgramInstruction::InitMultisig,
+        0,
+        event_authority,
+    )?;
+
+    Ok(())
+}
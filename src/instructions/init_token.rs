@@ -0,0 +1,104 @@
+use crate::cpi::system::{check_system_program, create_pda_account};
+use crate::cpi::token::{
+    is_token_program, GetAccountDataSize, InitializeAccount3, TOKEN_2022_PROGRAM_ID,
+    TOKEN_ACCOUNT_LEN,
+};
+use crate::errors::MyProgramError;
+use crate::events::VaultEvent;
+use crate::instructions::ProgramInstruction;
+use crate::states::{load_ix_data, load_rent, DataLen, TokenVaultAccount, VaultState};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InitTokenVault {
+    pub bump: u8,
+}
+
+impl DataLen for InitTokenVault {
+    const LEN: usize = core::mem::size_of::<InitTokenVault>();
+}
+
+pub fn init_token_vault(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault, mint, rent, system_program, token_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !user.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    check_system_program(system_program)?;
+
+    if !is_token_program(token_program.key()) {
+        return Err(MyProgramError::InvalidTokenProgram.into());
+    }
+
+    let init_ix_data = unsafe { load_ix_data::<InitTokenVault>(data)? };
+
+    TokenVaultAccount::check_uninitialized(vault, user.key(), mint.key(), init_ix_data.bump)?;
+    TokenVaultAccount::mint_decimals(mint, token_program.key())?;
+
+    // Token-2022 mints may require account extensions (transfer fee, transfer hook),
+    // so ask the token program how much space the vault needs.
+    let space = if token_program.key() == &TOKEN_2022_PROGRAM_ID {
+        GetAccountDataSize {
+            mint,
+            token_program: token_program.key(),
+        }
+        .invoke()?
+    } else {
+        TOKEN_ACCOUNT_LEN
+    };
+
+    let rent = load_rent(rent)?;
+
+    let bump_bytes = [init_ix_data.bump];
+
+    let signer_seeds = [
+        Seed::from(VaultState::SEED.as_bytes()),
+        Seed::from(user.key()),
+        Seed::from(mint.key()),
+        Seed::from(&bump_bytes[..]),
+    ];
+
+    let signer = [Signer::from(&signer_seeds[..])];
+
+    create_pda_account(
+        user,
+        vault,
+        space,
+        rent.minimum_balance(space),
+        token_program.key(),
+        &signer,
+    )?;
+
+    // The vault is its own authority, so only this program can move its tokens.
+    InitializeAccount3 {
+        account: vault,
+        mint,
+        owner: vault.key(),
+        token_program: token_program.key(),
+    }
+    .invoke()?;
+
+    VaultEvent {
+        instruction: ProgramInstruction::InitTokenVault,
+        vault: vault.key(),
+        owner: user.key(),
+        amount: 0,
+        balance: 0,
+    }
+    .emit(event_authority)?;
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::cpi::system::check_system_program;
+use crate::instructions::{init::create_vault, Init, ProgramInstruction};
+use crate::states::load_ix_data;
+
+/// `Init` without the rent sysvar account; rent is read through the `Rent::get()`
+/// syscall. Takes `Init`'s data.
+pub fn init_vault_v2(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault_pda, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !user.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    check_system_program(system_program)?;
+
+    let init_ix_data = unsafe { load_ix_data::<Init>(data)? };
+
+    create_vault(
+        user,
+        vault_pda,
+        &Rent::get()?,
+        init_ix_data.unlock_timestamp,
+        init_ix_data.withdraw_cooldown,
+        &init_ix_data.label,
+        0,
+    )?
+    .emit_event(ProgramInstruction::InitV2, 0, event_authority)?;
+
+    Ok(())
+}
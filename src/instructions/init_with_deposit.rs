@@ -0,0 +1,59 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::cpi::system::check_system_program;
+use crate::errors::MyProgramError;
+use crate::instructions::{init::create_vault, ProgramInstruction};
+use crate::states::{load_ix_data, load_rent, DataLen};
+
+/// `Init`'s data with `amount` appended.
+#[repr(C)]
+pub struct InitWithDeposit {
+    pub unlock_timestamp: i64,
+    pub withdraw_cooldown: i64,
+    pub label: [u8; 32],
+    pub amount: u64,
+}
+
+impl DataLen for InitWithDeposit {
+    const LEN: usize = core::mem::size_of::<InitWithDeposit>();
+}
+
+/// `Init` followed by a first deposit of `amount`, funded by the same `CreateAccount`.
+pub fn init_vault_with_deposit(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault_pda, rent, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !user.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    check_system_program(system_program)?;
+
+    let init_ix_data = unsafe { load_ix_data::<InitWithDeposit>(data)? };
+
+    if init_ix_data.amount.eq(&0) {
+        return Err(MyProgramError::ZeroAmount.into());
+    };
+
+    create_vault(
+        user,
+        vault_pda,
+        &load_rent(rent)?,
+        init_ix_data.unlock_timestamp,
+        init_ix_data.withdraw_cooldown,
+        &init_ix_data.label,
+        init_ix_data.amount,
+    )?
+    .emit_event(
+        ProgramInstruction::InitWithDeposit,
+        init_ix_data.amount,
+        event_authority,
+    )?;
+
+    Ok(())
+}
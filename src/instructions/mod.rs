@@ -1,21 +1,140 @@
 use pinocchio::program_error::ProgramError;
 
+use crate::errors::MyProgramError;
+
+pub mod accept_owner;
+pub mod approve;
+pub mod cancel_withdraw;
 pub mod close;
+pub mod close_token;
+pub mod close_v0;
+pub mod delegate_withdraw;
 pub mod deposit;
-pub mod withdraw;
+pub mod deposit_token;
+pub mod emit_event;
+pub mod execute_withdraw;
 pub mod init;
+pub mod init_idempotent;
+pub mod init_multisig;
+pub mod init_token;
+pub mod init_v2;
+pub mod init_with_deposit;
+pub mod multisig_close;
+pub mod multisig_withdraw;
+pub mod propose_owner;
+pub mod request_withdraw;
+pub mod revoke;
+pub mod set_cooldown;
+pub mod set_deposit_policy;
+pub mod set_lock;
+pub mod set_withdraw_limit;
+pub mod withdraw;
+pub mod withdraw_all;
+pub mod withdraw_token;
+pub mod withdraw_v2;
 
-pub use init::*;
+pub use accept_owner::*;
+pub use approve::*;
+pub use cancel_withdraw::*;
 pub use close::*;
+pub use close_token::*;
+pub use close_v0::*;
+pub use delegate_withdraw::*;
 pub use deposit::*;
+pub use deposit_token::*;
+pub use emit_event::*;
+pub use execute_withdraw::*;
+pub use init::*;
+pub use init_idempotent::*;
+pub use init_multisig::*;
+pub use init_token::*;
+pub use init_v2::*;
+pub use init_with_deposit::*;
+pub use multisig_close::*;
+pub use multisig_withdraw::*;
+pub use propose_owner::*;
+pub use request_withdraw::*;
+pub use revoke::*;
+pub use set_cooldown::*;
+pub use set_deposit_policy::*;
+pub use set_lock::*;
+pub use set_withdraw_limit::*;
 pub use withdraw::*;
+pub use withdraw_all::*;
+pub use withdraw_token::*;
+pub use withdraw_v2::*;
 
 #[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ProgramInstruction {
     Init,
     Deposit,
     Withdraw,
     Close,
+    InitTokenVault,
+    DepositToken,
+    WithdrawToken,
+    CloseTokenVault,
+    SetLock,
+    RequestWithdraw,
+    ExecuteWithdraw,
+    CancelWithdraw,
+    SetCooldown,
+    SetWithdrawLimit,
+    Approve,
+    Revoke,
+    DelegateWithdraw,
+    InitMultisig,
+    MultisigWithdraw,
+    MultisigClose,
+    ProposeOwner,
+    AcceptOwner,
+    WithdrawAll,
+    SetDepositPolicy,
+    InitWithDeposit,
+    InitIdempotent,
+    EmitEvent,
+    InitV2,
+    WithdrawV2,
+    CloseV0,
+}
+
+impl ProgramInstruction {
+    /// Snake-case name used in event logs.
+    pub const fn name(self) -> &'static str {
+        match self {
+            ProgramInstruction::Init => "init",
+            ProgramInstruction::Deposit => "deposit",
+            ProgramInstruction::Withdraw => "withdraw",
+            ProgramInstruction::Close => "close",
+            ProgramInstruction::InitTokenVault => "init_token_vault",
+            ProgramInstruction::DepositToken => "deposit_token",
+            ProgramInstruction::WithdrawToken => "withdraw_token",
+            ProgramInstruction::CloseTokenVault => "close_token_vault",
+            ProgramInstruction::SetLock => "set_lock",
+            ProgramInstruction::RequestWithdraw => "request_withdraw",
+            ProgramInstruction::ExecuteWithdraw => "execute_withdraw",
+            ProgramInstruction::CancelWithdraw => "cancel_withdraw",
+            ProgramInstruction::SetCooldown => "set_cooldown",
+            ProgramInstruction::SetWithdrawLimit => "set_withdraw_limit",
+            ProgramInstruction::Approve => "approve",
+            ProgramInstruction::Revoke => "revoke",
+            ProgramInstruction::DelegateWithdraw => "delegate_withdraw",
+            ProgramInstruction::InitMultisig => "init_multisig",
+            ProgramInstruction::MultisigWithdraw => "multisig_withdraw",
+            ProgramInstruction::MultisigClose => "multisig_close",
+            ProgramInstruction::ProposeOwner => "propose_owner",
+            ProgramInstruction::AcceptOwner => "accept_owner",
+            ProgramInstruction::WithdrawAll => "withdraw_all",
+            ProgramInstruction::SetDepositPolicy => "set_deposit_policy",
+            ProgramInstruction::InitWithDeposit => "init_with_deposit",
+            ProgramInstruction::InitIdempotent => "init_idempotent",
+            ProgramInstruction::EmitEvent => "emit_event",
+            ProgramInstruction::InitV2 => "init_v2",
+            ProgramInstruction::WithdrawV2 => "withdraw_v2",
+            ProgramInstruction::CloseV0 => "close_v0",
+        }
+    }
 }
 
 impl TryFrom<&u8> for ProgramInstruction {
@@ -27,7 +146,33 @@ impl TryFrom<&u8> for ProgramInstruction {
             1 => Ok(ProgramInstruction::Deposit),
             2 => Ok(ProgramInstruction::Withdraw),
             3 => Ok(ProgramInstruction::Close),
-            _ => Err(ProgramError::InvalidInstructionData),
+            4 => Ok(ProgramInstruction::InitTokenVault),
+            5 => Ok(ProgramInstruction::DepositToken),
+            6 => Ok(ProgramInstruction::WithdrawToken),
+            7 => Ok(ProgramInstruction::CloseTokenVault),
+            8 => Ok(ProgramInstruction::SetLock),
+            9 => Ok(ProgramInstruction::RequestWithdraw),
+            10 => Ok(ProgramInstruction::ExecuteWithdraw),
+            11 => Ok(ProgramInstruction::CancelWithdraw),
+            12 => Ok(ProgramInstruction::SetCooldown),
+            13 => Ok(ProgramInstruction::SetWithdrawLimit),
+            14 => Ok(ProgramInstruction::Approve),
+            15 => Ok(ProgramInstruction::Revoke),
+            16 => Ok(ProgramInstruction::DelegateWithdraw),
+            17 => Ok(ProgramInstruction::InitMultisig),
+            18 => Ok(ProgramInstruction::MultisigWithdraw),
+            19 => Ok(ProgramInstruction::MultisigClose),
+            20 => Ok(ProgramInstruction::ProposeOwner),
+            21 => Ok(ProgramInstruction::AcceptOwner),
+            22 => Ok(ProgramInstruction::WithdrawAll),
+            23 => Ok(ProgramInstruction::SetDepositPolicy),
+            24 => Ok(ProgramInstruction::InitWithDeposit),
+            25 => Ok(ProgramInstruction::InitIdempotent),
+            26 => Ok(ProgramInstruction::EmitEvent),
+            27 => Ok(ProgramInstruction::InitV2),
+            28 => Ok(ProgramInstruction::WithdrawV2),
+            29 => Ok(ProgramInstruction::CloseV0),
+            _ => Err(MyProgramError::InvalidInstructionData.into()),
         }
     }
 }
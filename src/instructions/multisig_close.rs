@@ -0,0 +1,46 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::errors::MyProgramError;
+use crate::events::VaultEvent;
+use crate::instructions::{Close, ProgramInstruction};
+use crate::states::{close_program_account, load_ix_data, MultisigVaultAccount};
+
+/// Drains a multisig vault to `recipient` once enough members signed.
+/// Takes the same data as `Close`.
+pub fn multisig_close(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [vault, recipient, signers @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    unsafe { load_ix_data::<Close>(data)? };
+
+    let vault_account = MultisigVaultAccount::load(vault)?;
+    vault_account.check_threshold(signers)?;
+
+    if !recipient.is_writable() {
+        return Err(MyProgramError::AccountNotWritable.into());
+    }
+    if recipient.key() == vault.key() {
+        return Err(MyProgramError::InvalidDestination.into());
+    }
+
+    let creator = vault_account.state().creator;
+    let amount = vault.lamports();
+
+    close_program_account(vault, recipient)?;
+
+    VaultEvent {
+        instruction: ProgramInstruction::MultisigClose,
+        vault: vault.key(),
+        owner: &creator,
+        amount,
+        balance: 0,
+    }
+    .emit(event_authority)?;
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::cpi::system::check_system_program;
+use crate::errors::MyProgramError;
+use crate::instructions::{ProgramInstruction, Withdraw};
+use crate::states::{load_ix_data, load_rent, MultisigVaultAccount};
+
+/// Withdraws from a multisig vault once enough members signed.
+/// Takes the same data as `Withdraw`.
+pub fn multisig_withdraw(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [vault, recipient, rent_sysvar, system_program, signers @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_system_program(system_program)?;
+
+    let withdraw_ix_data = unsafe { load_ix_data::<Withdraw>(data)? };
+
+    if withdraw_ix_data.amount.eq(&0) {
+        return Err(MyProgramError::ZeroAmount.into());
+    };
+
+    let vault_account = MultisigVaultAccount::load(vault)?;
+    vault_account.check_threshold(signers)?;
+
+    if !recipient.is_writable() {
+        return Err(MyProgramError::AccountNotWritable.into());
+    }
+    if recipient.key() == vault.key() {
+        return Err(MyProgramError::InvalidDestination.into());
+    }
+
+    let rent = load_rent(rent_sysvar)?;
+
+    vault_account.transfer_lamports(recipient, withdraw_ix_data.amount, &rent)?;
+
+    vault_account.emit_event(
+        ProgramInstruction::MultisigWithdraw,
+        withdraw_ix_data.amount,
+        event_authority,
+    )?;
+
+    Ok(())
+}
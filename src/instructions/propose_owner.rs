@@ -0,0 +1,38 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::instructions::ProgramInstruction;
+use crate::states::{load_ix_data, DataLen, VaultAccount};
+
+#[repr(C)]
+pub struct ProposeOwner;
+
+impl DataLen for ProposeOwner {
+    const LEN: usize = core::mem::size_of::<ProposeOwner>();
+}
+
+/// Records `new_owner` as the pending owner, replacing any earlier proposal.
+/// Ownership only moves once `new_owner` signs `AcceptOwner`.
+pub fn propose_owner(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault, new_owner] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !user.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    unsafe { load_ix_data::<ProposeOwner>(data)? };
+
+    let mut vault_account = VaultAccount::load(vault)?;
+    vault_account.check_owner(user)?;
+
+    vault_account.state_mut().pending_owner = *new_owner.key();
+
+    vault_account.emit_event(ProgramInstruction::ProposeOwner, 0, event_authority)?;
+
+    Ok(())
+}
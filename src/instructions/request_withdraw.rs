@@ -0,0 +1,52 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::errors::MyProgramError;
+use crate::instructions::{ProgramInstruction, Withdraw};
+use crate::states::{load_ix_data, VaultAccount};
+
+/// Records a pending withdrawal that `ExecuteWithdraw` can release once the
+/// vault's cooldown has elapsed. Takes the same data as `Withdraw`.
+pub fn request_withdraw(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !user.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    let withdraw_ix_data = unsafe { load_ix_data::<Withdraw>(data)? };
+
+    if withdraw_ix_data.amount.eq(&0) {
+        return Err(MyProgramError::ZeroAmount.into());
+    };
+
+    let mut vault_account = VaultAccount::load(vault)?;
+    vault_account.check_owner(user)?;
+
+    if vault.lamports() < withdraw_ix_data.amount {
+        return Err(MyProgramError::InsufficientVaultBalance.into());
+    }
+
+    let vault_state = vault_account.state_mut();
+
+    if vault_state.pending_withdraw_amount != 0 {
+        return Err(MyProgramError::WithdrawalAlreadyPending.into());
+    }
+
+    vault_state.pending_withdraw_amount = withdraw_ix_data.amount;
+    vault_state.pending_withdraw_requested_at = Clock::get()?.unix_timestamp;
+
+    vault_account.emit_event(ProgramInstruction::RequestWithdraw, 0, event_authority)?;
+
+    Ok(())
+}
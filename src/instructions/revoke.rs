@@ -0,0 +1,36 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::instructions::ProgramInstruction;
+use crate::states::{load_ix_data, DataLen, VaultAccount};
+
+#[repr(C)]
+pub struct Revoke;
+
+impl DataLen for Revoke {
+    const LEN: usize = core::mem::size_of::<Revoke>();
+}
+
+pub fn revoke_delegate(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !user.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    unsafe { load_ix_data::<Revoke>(data)? };
+
+    let mut vault_account = VaultAccount::load(vault)?;
+    vault_account.check_owner(user)?;
+
+    vault_account.state_mut().clear_delegate();
+
+    vault_account.emit_event(ProgramInstruction::Revoke, 0, event_authority)?;
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::errors::MyProgramError;
+use crate::instructions::ProgramInstruction;
+use crate::states::{load_ix_data, DataLen, VaultAccount};
+
+#[repr(C)]
+pub struct SetCooldown {
+    pub withdraw_cooldown: i64,
+}
+
+impl DataLen for SetCooldown {
+    const LEN: usize = core::mem::size_of::<SetCooldown>();
+}
+
+/// Sets the withdrawal cooldown. Like the time lock, it can only be lengthened,
+/// so a leaked owner key cannot remove it.
+pub fn set_withdraw_cooldown(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !user.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    let set_cooldown_ix_data = unsafe { load_ix_data::<SetCooldown>(data)? };
+
+    let mut vault_account = VaultAccount::load(vault)?;
+    vault_account.check_owner(user)?;
+
+    let vault_state = vault_account.state_mut();
+
+    if set_cooldown_ix_data.withdraw_cooldown < vault_state.withdraw_cooldown {
+        return Err(MyProgramError::CooldownShortened.into());
+    }
+
+    vault_state.withdraw_cooldown = set_cooldown_ix_data.withdraw_cooldown;
+
+    vault_account.emit_event(ProgramInstruction::SetCooldown, 0, event_authority)?;
+
+    Ok(())
+}
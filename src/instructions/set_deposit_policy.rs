@@ -0,0 +1,44 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::errors::MyProgramError;
+use crate::instructions::ProgramInstruction;
+use crate::states::{load_ix_data, DataLen, VaultAccount};
+
+#[repr(C)]
+pub struct SetDepositPolicy {
+    /// 1 to accept deposits from the owner only, 0 to accept them from anyone.
+    pub owner_only: u8,
+}
+
+impl DataLen for SetDepositPolicy {
+    const LEN: usize = core::mem::size_of::<SetDepositPolicy>();
+}
+
+pub fn set_deposit_policy(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !user.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    let policy_ix_data = unsafe { load_ix_data::<SetDepositPolicy>(data)? };
+
+    if policy_ix_data.owner_only > 1 {
+        return Err(MyProgramError::InvalidInstructionData.into());
+    }
+
+    let mut vault_account = VaultAccount::load(vault)?;
+    vault_account.check_owner(user)?;
+
+    vault_account.state_mut().owner_only_deposits = policy_ix_data.owner_only;
+
+    vault_account.emit_event(ProgramInstruction::SetDepositPolicy, 0, event_authority)?;
+
+    Ok(())
+}
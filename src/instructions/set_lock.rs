@@ -0,0 +1,46 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::errors::MyProgramError;
+use crate::instructions::ProgramInstruction;
+use crate::states::{load_ix_data, DataLen, VaultAccount};
+
+#[repr(C)]
+pub struct SetLock {
+    pub unlock_timestamp: i64,
+}
+
+impl DataLen for SetLock {
+    const LEN: usize = core::mem::size_of::<SetLock>();
+}
+
+/// Moves the vault's unlock timestamp. The lock can only be extended.
+pub fn set_vault_lock(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !user.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    let set_lock_ix_data = unsafe { load_ix_data::<SetLock>(data)? };
+
+    let mut vault_account = VaultAccount::load(vault)?;
+    vault_account.check_owner(user)?;
+
+    let vault_state = vault_account.state_mut();
+
+    if set_lock_ix_data.unlock_timestamp < vault_state.unlock_timestamp {
+        return Err(MyProgramError::LockShortened.into());
+    }
+
+    vault_state.unlock_timestamp = set_lock_ix_data.unlock_timestamp;
+
+    vault_account.emit_event(ProgramInstruction::SetLock, 0, event_authority)?;
+
+    Ok(())
+}
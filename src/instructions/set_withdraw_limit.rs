@@ -0,0 +1,57 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::errors::MyProgramError;
+use crate::instructions::ProgramInstruction;
+use crate::states::{load_ix_data, DataLen, VaultAccount};
+
+#[repr(C)]
+pub struct SetWithdrawLimit {
+    pub withdraw_limit: u64,
+    pub limit_period: i64,
+}
+
+impl DataLen for SetWithdrawLimit {
+    const LEN: usize = core::mem::size_of::<SetWithdrawLimit>();
+}
+
+/// Caps how many lamports can leave the vault per `limit_period` seconds.
+///
+/// Once set, the limit can only be tightened: a lower amount or a longer period.
+pub fn set_withdraw_limit(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !user.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    let limit_ix_data = unsafe { load_ix_data::<SetWithdrawLimit>(data)? };
+
+    if limit_ix_data.withdraw_limit == 0 || limit_ix_data.limit_period <= 0 {
+        return Err(MyProgramError::InvalidInstructionData.into());
+    }
+
+    let mut vault_account = VaultAccount::load(vault)?;
+    vault_account.check_owner(user)?;
+
+    let vault_state = vault_account.state_mut();
+
+    if vault_state.withdraw_limit != 0
+        && (limit_ix_data.withdraw_limit > vault_state.withdraw_limit
+            || limit_ix_data.limit_period < vault_state.limit_period)
+    {
+        return Err(MyProgramError::WithdrawLimitLoosened.into());
+    }
+
+    vault_state.withdraw_limit = limit_ix_data.withdraw_limit;
+    vault_state.limit_period = limit_ix_data.limit_period;
+
+    vault_account.emit_event(ProgramInstruction::SetWithdrawLimit, 0, event_authority)?;
+
+    Ok(())
+}
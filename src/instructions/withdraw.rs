@@ -2,67 +2,91 @@ use pinocchio::{
     account_info::AccountInfo, program_error::ProgramError, sysvars::rent::Rent, ProgramResult,
 };
 
-use crate::states::{load_acc_unchecked, load_ix_data, DataLen, VaultState};
+use crate::cpi::system::check_system_program;
+use crate::errors::MyProgramError;
+use crate::instructions::ProgramInstruction;
+use crate::states::{load_ix_data, load_rent, DataLen, VaultAccount};
 
 #[repr(C)]
 pub struct Withdraw {
     pub amount: u64,
-    pub bump: u8,
 }
 
 impl DataLen for Withdraw {
     const LEN: usize = core::mem::size_of::<Withdraw>();
 }
 
-pub fn withdraw_from_vault(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
-    let [user, vault, rent_sysvar, _] = accounts else {
+/// Pays `amount` to the optional trailing `recipient` account, or to `user`
+/// when none is passed.
+pub fn withdraw_from_vault(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault, rent_sysvar, system_program, rest @ ..] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
+    let recipient = match rest {
+        [] => user,
+        [recipient] => recipient,
+        _ => return Err(MyProgramError::TooManyAccounts.into()),
+    };
+
     if !user.is_signer() {
         return Err(ProgramError::MissingRequiredSignature);
     };
 
+    check_system_program(system_program)?;
+
+    withdraw(
+        ProgramInstruction::Withdraw,
+        user,
+        vault,
+        recipient,
+        &load_rent(rent_sysvar)?,
+        data,
+        event_authority,
+    )
+}
+
+/// Shared body of `Withdraw` and `WithdrawV2` once accounts are resolved; the event
+/// is logged under `instruction`.
+pub(crate) fn withdraw(
+    instruction: ProgramInstruction,
+    user: &AccountInfo,
+    vault: &AccountInfo,
+    recipient: &AccountInfo,
+    rent: &Rent,
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
     let withdraw_ix_data = unsafe { load_ix_data::<Withdraw>(data)? };
 
     if withdraw_ix_data.amount.eq(&0) {
-        return Err(ProgramError::InvalidInstructionData);
+        return Err(MyProgramError::ZeroAmount.into());
     };
 
-    VaultState::validate_pda(withdraw_ix_data.bump, vault.key(), user.key())?;
-
-    let vault_state = unsafe { load_acc_unchecked::<VaultState>(vault.borrow_data_unchecked()) }?;
+    let mut vault_account = VaultAccount::load(vault)?;
+    vault_account.check_owner(user)?;
+    vault_account.check_unlocked()?;
 
-    if vault_state.owner != *user.key() {
-        return Err(ProgramError::InvalidAccountOwner);
+    if vault_account.state().requires_withdraw_request() {
+        return Err(MyProgramError::WithdrawRequestRequired.into());
     }
 
-    //check if enough for this withdrwal
-    if vault.lamports() < withdraw_ix_data.amount {
-        return Err(ProgramError::InsufficientFunds);
-    };
+    if !recipient.is_writable() {
+        return Err(MyProgramError::AccountNotWritable.into());
+    }
+    if recipient.key() == vault.key() {
+        return Err(MyProgramError::InvalidDestination.into());
+    }
 
-    //check min amount after withdrawl should be enough for rent
-    let rent = Rent::from_account_info(rent_sysvar)?;
-    let min_balance = rent.minimum_balance(VaultState::LEN);
+    vault_account.record_withdrawal(withdraw_ix_data.amount)?;
 
-    if vault.lamports() - withdraw_ix_data.amount < min_balance {
-        return Err(ProgramError::InsufficientFunds);
-    }
+    vault_account.transfer_lamports(recipient, withdraw_ix_data.amount, rent)?;
 
-    // Move lamports directly. Since our program owns `vault` and it carries data,
-    // using a CPI to the system program would fail (system transfer requires empty data).
-    unsafe {
-        let vault_lamports = vault.borrow_mut_lamports_unchecked();
-        let user_lamports = user.borrow_mut_lamports_unchecked();
-
-        *vault_lamports = (*vault_lamports)
-            .checked_sub(withdraw_ix_data.amount)
-            .ok_or(ProgramError::InsufficientFunds)?;
-        *user_lamports = (*user_lamports)
-            .checked_add(withdraw_ix_data.amount)
-            .ok_or(ProgramError::InsufficientFunds)?;
-    }
+    vault_account.emit_event(instruction, withdraw_ix_data.amount, event_authority)?;
 
     Ok(())
 }
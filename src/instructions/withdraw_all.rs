@@ -0,0 +1,59 @@
+use pinocchio::{
+    account_info::AccountInfo, program::set_return_data, program_error::ProgramError, ProgramResult,
+};
+
+use crate::cpi::system::check_system_program;
+use crate::errors::MyProgramError;
+use crate::instructions::ProgramInstruction;
+use crate::states::{load_ix_data, load_rent, DataLen, VaultAccount, VaultState};
+
+#[repr(C)]
+pub struct WithdrawAll;
+
+impl DataLen for WithdrawAll {
+    const LEN: usize = core::mem::size_of::<WithdrawAll>();
+}
+
+/// Moves every lamport above the rent-exempt minimum to the owner. The amount is
+/// computed at execution time and returned as little-endian `u64` return data.
+pub fn withdraw_all_from_vault(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault, rent_sysvar, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !user.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    check_system_program(system_program)?;
+
+    unsafe { load_ix_data::<WithdrawAll>(data)? };
+
+    let mut vault_account = VaultAccount::load(vault)?;
+    vault_account.check_owner(user)?;
+    vault_account.check_unlocked()?;
+
+    if vault_account.state().requires_withdraw_request() {
+        return Err(MyProgramError::WithdrawRequestRequired.into());
+    }
+
+    let rent = load_rent(rent_sysvar)?;
+    let amount = vault
+        .lamports()
+        .saturating_sub(rent.minimum_balance(VaultState::LEN));
+
+    if amount > 0 {
+        vault_account.record_withdrawal(amount)?;
+        vault_account.transfer_lamports(user, amount, &rent)?;
+    }
+
+    vault_account.emit_event(ProgramInstruction::WithdrawAll, amount, event_authority)?;
+
+    set_return_data(&amount.to_le_bytes());
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::cpi::token::{is_token_program, TransferChecked};
+use crate::errors::MyProgramError;
+use crate::instructions::ProgramInstruction;
+use crate::states::{load_ix_data, DataLen, TokenVaultAccount, VaultState};
+
+#[repr(C)]
+pub struct WithdrawToken {
+    pub amount: u64,
+    pub bump: u8,
+}
+
+impl DataLen for WithdrawToken {
+    const LEN: usize = core::mem::size_of::<WithdrawToken>();
+}
+
+pub fn withdraw_token_from_vault(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault, user_token_account, mint, token_program, extra_accounts @ ..] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !user.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    if !is_token_program(token_program.key()) {
+        return Err(MyProgramError::InvalidTokenProgram.into());
+    }
+
+    let withdraw_ix_data = unsafe { load_ix_data::<WithdrawToken>(data)? };
+
+    if withdraw_ix_data.amount.eq(&0) {
+        return Err(MyProgramError::ZeroAmount.into());
+    };
+
+    let vault = TokenVaultAccount::load(
+        vault,
+        user.key(),
+        mint.key(),
+        token_program.key(),
+        withdraw_ix_data.bump,
+    )?;
+    let decimals = TokenVaultAccount::mint_decimals(mint, token_program.key())?;
+
+    if vault.amount() < withdraw_ix_data.amount {
+        return Err(MyProgramError::InsufficientVaultBalance.into());
+    }
+
+    let bump_bytes = [withdraw_ix_data.bump];
+
+    let signer_seeds = [
+        Seed::from(VaultState::SEED.as_bytes()),
+        Seed::from(user.key()),
+        Seed::from(mint.key()),
+        Seed::from(&bump_bytes[..]),
+    ];
+
+    TransferChecked {
+        from: vault.info(),
+        mint,
+        to: user_token_account,
+        authority: vault.info(),
+        extra_accounts,
+        amount: withdraw_ix_data.amount,
+        decimals,
+        token_program: token_program.key(),
+    }
+    .invoke_signed(&[Signer::from(&signer_seeds[..])])?;
+
+    vault.emit_event(
+        ProgramInstruction::WithdrawToken,
+        user.key(),
+        withdraw_ix_data.amount,
+        event_authority,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::cpi::system::check_system_program;
+use crate::errors::MyProgramError;
+use crate::instructions::{withdraw::withdraw, ProgramInstruction};
+
+/// `Withdraw` without the rent sysvar account; rent is read through the `Rent::get()`
+/// syscall. Takes `Withdraw`'s data and the same optional trailing `recipient`.
+pub fn withdraw_from_vault_v2(
+    accounts: &[AccountInfo],
+    data: &[u8],
+    event_authority: Option<&AccountInfo>,
+) -> ProgramResult {
+    let [user, vault, system_program, rest @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let recipient = match rest {
+        [] => user,
+        [recipient] => recipient,
+        _ => return Err(MyProgramError::TooManyAccounts.into()),
+    };
+
+    if !user.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    check_system_program(system_program)?;
+
+    withdraw(
+        ProgramInstruction::WithdrawV2,
+        user,
+        vault,
+        recipient,
+        &Rent::get()?,
+        data,
+        event_authority,
+    )
+}
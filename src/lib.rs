@@ -6,8 +6,10 @@ mod entrypoint;
 #[cfg(feature = "std")]
 extern crate std;
 
+pub mod cpi;
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod states;
 
-pinocchio_pubkey::declare_id!("63vgRZotq9C4krvqWcVjWHgw1gaZTXuYu76sSbosq6ca");
\ No newline at end of file
+pinocchio_pubkey::declare_id!("63vgRZotq9C4krvqWcVjWHgw1gaZTXuYu76sSbosq6ca");
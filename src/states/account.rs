@@ -0,0 +1,211 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use super::{load_acc_unchecked, DataLen, VaultState};
+use crate::errors::MyProgramError;
+use crate::events::VaultEvent;
+use crate::instructions::ProgramInstruction;
+
+/// A vault account that passed every ownership and layout check.
+///
+/// Handlers go through this wrapper instead of reading `VaultState` directly,
+/// so owner, length, discriminator, writability and PDA are validated in one place.
+pub struct VaultAccount<'a> {
+    info: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a AccountInfo> for VaultAccount<'a> {
+    type Error = ProgramError;
+
+    fn try_from(info: &'a AccountInfo) -> Result<Self, Self::Error> {
+        check_program_account(info)?;
+
+        let state = unsafe { load_acc_unchecked::<VaultState>(info.borrow_data_unchecked())? };
+        state.validate_pda(info.key())?;
+
+        Ok(Self { info })
+    }
+}
+
+impl<'a> VaultAccount<'a> {
+    /// Loads a vault and checks that it sits at the PDA derived from its stored creator and bump.
+    pub fn load(info: &'a AccountInfo) -> Result<Self, ProgramError> {
+        Self::try_from(info)
+    }
+
+    /// Checks an account that is about to be created as `owner`'s vault named `label`
+    /// and returns the canonical bump of its address.
+    pub fn check_uninitialized(
+        info: &AccountInfo,
+        owner: &Pubkey,
+        label: &[u8; 32],
+    ) -> Result<u8, ProgramError> {
+        if !info.is_writable() {
+            return Err(MyProgramError::AccountNotWritable.into());
+        }
+        if !info.data_is_empty() {
+            return Err(MyProgramError::VaultAlreadyInitialized.into());
+        }
+        let (pda, bump) = VaultState::find_pda(owner, label);
+        if pda != *info.key() {
+            return Err(MyProgramError::PdaMismatch.into());
+        }
+        Ok(bump)
+    }
+
+    /// Fails unless `signer` is the owner recorded in the vault state.
+    pub fn check_owner(&self, signer: &AccountInfo) -> ProgramResult {
+        if self.state().owner != *signer.key() {
+            return Err(MyProgramError::InvalidOwner.into());
+        }
+        Ok(())
+    }
+
+    /// Fails if the vault only accepts deposits from its owner and `depositor` isn't it.
+    pub fn check_depositor(&self, depositor: &AccountInfo) -> ProgramResult {
+        if self.state().owner_only_deposits != 0 {
+            return self.check_owner(depositor);
+        }
+        Ok(())
+    }
+
+    /// Fails while the vault's unlock timestamp is still in the future.
+    pub fn check_unlocked(&self) -> ProgramResult {
+        if self.state().is_locked(Clock::get()?.unix_timestamp) {
+            return Err(MyProgramError::VaultLocked.into());
+        }
+        Ok(())
+    }
+
+    /// Counts `amount` against the vault's withdrawal rate limit.
+    pub fn record_withdrawal(&mut self, amount: u64) -> ProgramResult {
+        let now = Clock::get()?.unix_timestamp;
+        self.state_mut().record_withdrawal(amount, now)
+    }
+
+    /// Moves `amount` lamports to `recipient`, keeping the vault rent exempt.
+    pub fn transfer_lamports(
+        &self,
+        recipient: &AccountInfo,
+        amount: u64,
+        rent: &Rent,
+    ) -> ProgramResult {
+        transfer_program_lamports(
+            self.info,
+            recipient,
+            amount,
+            rent.minimum_balance(VaultState::LEN),
+        )
+    }
+
+    /// Emits `instruction`'s event with the vault's current owner and lamports.
+    pub fn emit_event(
+        &self,
+        instruction: ProgramInstruction,
+        amount: u64,
+        event_authority: Option<&AccountInfo>,
+    ) -> ProgramResult {
+        VaultEvent {
+            instruction,
+            vault: self.info.key(),
+            owner: &self.state().owner,
+            amount,
+            balance: self.info.lamports(),
+        }
+        .emit(event_authority)
+    }
+
+    /// Drains the vault into `recipient` and hands the account back to the system program.
+    pub fn close(self, recipient: &AccountInfo) -> ProgramResult {
+        close_program_account(self.info, recipient)
+    }
+
+    pub fn info(&self) -> &'a AccountInfo {
+        self.info
+    }
+
+    pub fn state(&self) -> &VaultState {
+        // Layout was validated when the wrapper was built.
+        unsafe { &*(self.info.borrow_data_unchecked().as_ptr() as *const VaultState) }
+    }
+
+    pub fn state_mut(&mut self) -> &mut VaultState {
+        unsafe { &mut *(self.info.borrow_mut_data_unchecked().as_mut_ptr() as *mut VaultState) }
+    }
+}
+
+/// Fails unless `info` is a writable account owned by this program.
+///
+/// An empty account not owned by the program is reported as an uninitialized
+/// vault, so closed and never-created addresses fail the same way.
+pub fn check_program_account(info: &AccountInfo) -> ProgramResult {
+    if !info.is_owned_by(&crate::ID) {
+        return Err(if info.data_is_empty() {
+            MyProgramError::VaultNotInitialized.into()
+        } else {
+            MyProgramError::InvalidProgramOwner.into()
+        });
+    }
+    if !info.is_writable() {
+        return Err(MyProgramError::AccountNotWritable.into());
+    }
+    Ok(())
+}
+
+/// Moves `amount` lamports from the program-owned `info` to `recipient`, leaving
+/// at least `min_balance` behind.
+pub fn transfer_program_lamports(
+    info: &AccountInfo,
+    recipient: &AccountInfo,
+    amount: u64,
+    min_balance: u64,
+) -> ProgramResult {
+    let remaining = info
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(MyProgramError::InsufficientVaultBalance)?;
+    if remaining < min_balance {
+        return Err(MyProgramError::BelowRentMinimum.into());
+    }
+
+    // Move lamports directly. Since our program owns `info` and it carries data,
+    // using a CPI to the system program would fail (system transfer requires empty data).
+    unsafe {
+        let recipient_lamports = recipient.borrow_mut_lamports_unchecked();
+        *recipient_lamports = (*recipient_lamports)
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        *info.borrow_mut_lamports_unchecked() = remaining;
+    }
+
+    Ok(())
+}
+
+/// Moves every lamport of `info` to `recipient`, zeroes and truncates its data
+/// and reassigns it to the system program.
+///
+/// Draining alone leaves the old state readable until the end of the transaction,
+/// so lamports sent back to the address within the same transaction would revive
+/// it. After this, the address is an empty system account that no loader accepts.
+pub fn close_program_account(info: &AccountInfo, recipient: &AccountInfo) -> ProgramResult {
+    // Move lamports directly; system transfer would reject `from` with data.
+    unsafe {
+        let info_lamports = info.borrow_mut_lamports_unchecked();
+        let recipient_lamports = recipient.borrow_mut_lamports_unchecked();
+        *recipient_lamports = (*recipient_lamports)
+            .checked_add(*info_lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        *info_lamports = 0;
+    }
+
+    info.try_borrow_mut_data()?.fill(0);
+    info.resize(0)?;
+    unsafe { info.assign(&pinocchio_system::ID) };
+
+    Ok(())
+}
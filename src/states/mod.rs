@@ -1,5 +1,11 @@
+pub mod account;
+pub mod multisig;
 pub mod state;
+pub mod token_vault;
 pub mod utils;
 
+pub use account::*;
+pub use multisig::*;
 pub use state::*;
-pub use utils::*;
\ No newline at end of file
+pub use token_vault::*;
+pub use utils::*;
@@ -0,0 +1,228 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+    sysvars::rent::Rent,
+    ProgramResult,
+};
+
+use super::account::{check_program_account, transfer_program_lamports};
+use super::utils::{
+    load_acc_mut_uninit, load_acc_unchecked, AccountDiscriminator, DataLen, Discriminator,
+};
+use pinocchio_pubkey::derive_address;
+
+use crate::errors::MyProgramError;
+use crate::events::VaultEvent;
+use crate::instructions::ProgramInstruction;
+
+/// Maximum number of members a multisig vault can hold.
+pub const MAX_MULTISIG_SIGNERS: usize = 10;
+
+/// Vault controlled by `threshold` of up to `MAX_MULTISIG_SIGNERS` members.
+///
+/// The address is derived from the creator and a caller-chosen seed, so it does
+/// not change with membership.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MultisigVaultState {
+    pub discriminator: u8,
+    pub version: u8,
+    /// Number of member signatures required by withdraw and close.
+    pub threshold: u8,
+    /// Number of populated entries in `signers`.
+    pub signer_count: u8,
+    /// Canonical bump of the vault PDA, checked once at init.
+    pub bump: u8,
+    pub _padding: [u8; 3],
+    pub creator: Pubkey,
+    pub seed: [u8; 32],
+    pub signers: [Pubkey; MAX_MULTISIG_SIGNERS],
+}
+
+impl DataLen for MultisigVaultState {
+    const LEN: usize = core::mem::size_of::<MultisigVaultState>();
+}
+
+impl Discriminator for MultisigVaultState {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::MultisigVault;
+    const VERSION: u8 = 1;
+}
+
+impl MultisigVaultState {
+    pub const SEED: &'static str = "multisig";
+
+    /// Returns the multisig vault address for `creator` and `seed` and its canonical bump.
+    pub fn find_pda(creator: &Pubkey, seed: &[u8; 32]) -> (Pubkey, u8) {
+        pubkey::find_program_address(&[Self::SEED.as_bytes(), creator, seed], &crate::ID)
+    }
+
+    /// Checks `pda` against the address derived from the stored creator, seed and bump.
+    pub fn validate_pda(&self, pda: &Pubkey) -> Result<(), ProgramError> {
+        let derived = derive_address(
+            &[Self::SEED.as_bytes(), &self.creator, &self.seed],
+            Some(self.bump),
+            &crate::ID,
+        );
+        if derived != *pda {
+            return Err(MyProgramError::PdaMismatch.into());
+        }
+        Ok(())
+    }
+
+    /// Writes a fresh state with the keys of `members`, rejecting duplicates
+    /// and thresholds outside `1..=members.len()`.
+    pub fn initialize(
+        vault_acc: &AccountInfo,
+        creator: &Pubkey,
+        seed: &[u8; 32],
+        bump: u8,
+        threshold: u8,
+        members: &[AccountInfo],
+    ) -> ProgramResult {
+        if members.is_empty() || members.len() > MAX_MULTISIG_SIGNERS {
+            return Err(MyProgramError::InvalidThreshold.into());
+        }
+        if threshold == 0 || threshold as usize > members.len() {
+            return Err(MyProgramError::InvalidThreshold.into());
+        }
+
+        let vault_state = unsafe {
+            load_acc_mut_uninit::<MultisigVaultState>(vault_acc.borrow_mut_data_unchecked())?
+        };
+
+        let mut signers = [[0; 32]; MAX_MULTISIG_SIGNERS];
+        for (i, member) in members.iter().enumerate() {
+            if signers[..i].contains(member.key()) {
+                return Err(MyProgramError::DuplicateSigner.into());
+            }
+            signers[i] = *member.key();
+        }
+
+        *vault_state = MultisigVaultState {
+            discriminator: Self::DISCRIMINATOR as u8,
+            version: Self::VERSION,
+            threshold,
+            signer_count: members.len() as u8,
+            bump,
+            _padding: [0; 3],
+            creator: *creator,
+            seed: *seed,
+            signers,
+        };
+
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn members(&self) -> &[Pubkey] {
+        &self.signers[..self.signer_count as usize]
+    }
+}
+
+/// A multisig vault account that passed every ownership and layout check.
+pub struct MultisigVaultAccount<'a> {
+    info: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a AccountInfo> for MultisigVaultAccount<'a> {
+    type Error = ProgramError;
+
+    fn try_from(info: &'a AccountInfo) -> Result<Self, Self::Error> {
+        check_program_account(info)?;
+
+        let state =
+            unsafe { load_acc_unchecked::<MultisigVaultState>(info.borrow_data_unchecked())? };
+        state.validate_pda(info.key())?;
+
+        Ok(Self { info })
+    }
+}
+
+impl<'a> MultisigVaultAccount<'a> {
+    /// Loads a multisig vault and checks it sits at the PDA of its stored creator, seed and bump.
+    pub fn load(info: &'a AccountInfo) -> Result<Self, ProgramError> {
+        Self::try_from(info)
+    }
+
+    /// Checks an account that is about to be created as a multisig vault and
+    /// returns the canonical bump of its address.
+    pub fn check_uninitialized(
+        info: &AccountInfo,
+        creator: &Pubkey,
+        seed: &[u8; 32],
+    ) -> Result<u8, ProgramError> {
+        if !info.is_writable() {
+            return Err(MyProgramError::AccountNotWritable.into());
+        }
+        if !info.data_is_empty() {
+            return Err(MyProgramError::VaultAlreadyInitialized.into());
+        }
+        let (pda, bump) = MultisigVaultState::find_pda(creator, seed);
+        if pda != *info.key() {
+            return Err(MyProgramError::PdaMismatch.into());
+        }
+        Ok(bump)
+    }
+
+    /// Fails unless at least `threshold` distinct members signed among `signers`.
+    ///
+    /// Non-member and non-signer accounts are ignored, and a member passed twice
+    /// is only counted once.
+    pub fn check_threshold(&self, signers: &[AccountInfo]) -> ProgramResult {
+        let members = self.state().members();
+        let mut approved: u16 = 0;
+
+        for signer in signers.iter().filter(|s| s.is_signer()) {
+            if let Some(i) = members.iter().position(|m| m == signer.key()) {
+                approved |= 1 << i;
+            }
+        }
+
+        if approved.count_ones() < self.state().threshold as u32 {
+            return Err(MyProgramError::ThresholdNotMet.into());
+        }
+        Ok(())
+    }
+
+    /// Moves `amount` lamports to `recipient`, keeping the vault rent exempt.
+    pub fn transfer_lamports(
+        &self,
+        recipient: &AccountInfo,
+        amount: u64,
+        rent: &Rent,
+    ) -> ProgramResult {
+        transfer_program_lamports(
+            self.info,
+            recipient,
+            amount,
+            rent.minimum_balance(MultisigVaultState::LEN),
+        )
+    }
+
+    /// Emits `instruction`'s event with the vault's creator and current lamports.
+    pub fn emit_event(
+        &self,
+        instruction: ProgramInstruction,
+        amount: u64,
+        event_authority: Option<&AccountInfo>,
+    ) -> ProgramResult {
+        VaultEvent {
+            instruction,
+            vault: self.info.key(),
+            owner: &self.state().creator,
+            amount,
+            balance: self.info.lamports(),
+        }
+        .emit(event_authority)
+    }
+
+    pub fn info(&self) -> &'a AccountInfo {
+        self.info
+    }
+
+    pub fn state(&self) -> &MultisigVaultState {
+        // Layout was validated when the wrapper was built.
+        unsafe { &*(self.info.borrow_data_unchecked().as_ptr() as *const MultisigVaultState) }
+    }
+}
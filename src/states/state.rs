@@ -1,4 +1,4 @@
-use super::utils::DataLen;
+use super::utils::{AccountDiscriminator, DataLen, Discriminator};
 use pinocchio::{
     account_info::AccountInfo,
     program_error::ProgramError,
@@ -6,36 +6,205 @@ use pinocchio::{
     ProgramResult,
 };
 
+use pinocchio_pubkey::derive_address;
+
 use crate::errors::MyProgramError;
-use crate::states::utils::load_acc_mut_unchecked;
+use crate::states::utils::load_acc_mut_uninit;
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct VaultState {
+    pub discriminator: u8,
+    pub version: u8,
     pub owner: Pubkey,
+    /// Canonical bump of the vault PDA, checked once at init.
+    pub bump: u8,
+    /// Non-zero when only the owner may deposit; anyone can deposit otherwise.
+    pub owner_only_deposits: u8,
+    pub _padding: [u8; 4],
+    /// Unix timestamp before which withdraw and close are rejected; 0 means unlocked.
+    pub unlock_timestamp: i64,
+    /// Seconds a withdrawal request must wait before execution; 0 disables the two-step flow.
+    pub withdraw_cooldown: i64,
+    /// Lamports of the pending withdrawal request; 0 when none is pending.
+    pub pending_withdraw_amount: u64,
+    /// Unix timestamp at which the pending withdrawal was requested.
+    pub pending_withdraw_requested_at: i64,
+    /// Maximum lamports that may leave the vault per period; 0 means unlimited.
+    pub withdraw_limit: u64,
+    /// Length of a rate-limit period in seconds.
+    pub limit_period: i64,
+    /// Unix timestamp at which the current rate-limit period started.
+    pub period_start: i64,
+    /// Lamports withdrawn since `period_start`.
+    pub withdrawn_in_period: u64,
+    /// Key allowed to withdraw on the owner's behalf; all zeros when none.
+    pub delegate: Pubkey,
+    /// Only account the delegate may withdraw to.
+    pub delegate_destination: Pubkey,
+    /// Lamports the delegate may still withdraw.
+    pub delegate_allowance: u64,
+    /// Unix timestamp after which the delegation is void; 0 means no expiry.
+    pub delegate_expiry: i64,
+    /// Key the vault was created by; the PDA is derived from it so it survives owner changes.
+    pub creator: Pubkey,
+    /// Owner proposed by `ProposeOwner`; all zeros when none.
+    pub pending_owner: Pubkey,
+    /// Caller-chosen name that lets one creator open several vaults; part of the PDA seeds
+    /// unless it is all zeros.
+    pub label: [u8; 32],
 }
 
 impl DataLen for VaultState {
     const LEN: usize = core::mem::size_of::<VaultState>();
 }
 
+impl Discriminator for VaultState {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::Vault;
+    const VERSION: u8 = 1;
+}
+
 impl VaultState {
     pub const SEED: &'static str = "vault";
 
-    pub fn validate_pda(bump: u8, pda: &Pubkey, owner: &Pubkey) -> Result<(), ProgramError> {
-        let seed_with_bump = &[Self::SEED.as_bytes(), owner, &[bump]];
-        let derived = pubkey::create_program_address(seed_with_bump, &crate::ID)?;
+    /// Seed prefix of vaults with a non-default label.
+    pub const LABELED_SEED: &'static str = "labeled_vault";
+
+    /// Label of a creator's default vault.
+    pub const DEFAULT_LABEL: [u8; 32] = [0; 32];
+
+    /// Size of vaults created by the first deployment, which stored only the owner.
+    pub const V0_LEN: usize = 32;
+
+    pub fn new(owner: Pubkey, label: [u8; 32], bump: u8) -> Self {
+        Self {
+            discriminator: Self::DISCRIMINATOR as u8,
+            version: Self::VERSION,
+            owner,
+            bump,
+            owner_only_deposits: 0,
+            _padding: [0; 4],
+            unlock_timestamp: 0,
+            withdraw_cooldown: 0,
+            pending_withdraw_amount: 0,
+            pending_withdraw_requested_at: 0,
+            withdraw_limit: 0,
+            limit_period: 0,
+            period_start: 0,
+            withdrawn_in_period: 0,
+            delegate: [0; 32],
+            delegate_destination: [0; 32],
+            delegate_allowance: 0,
+            delegate_expiry: 0,
+            creator: owner,
+            pending_owner: [0; 32],
+            label,
+        }
+    }
+
+    #[inline(always)]
+    pub fn is_locked(&self, now: i64) -> bool {
+        now < self.unlock_timestamp
+    }
+
+    /// Instant withdrawals and closing a funded vault are disabled while a cooldown is set.
+    #[inline(always)]
+    pub fn requires_withdraw_request(&self) -> bool {
+        self.withdraw_cooldown > 0
+    }
+
+    #[inline(always)]
+    pub fn delegation_expired(&self, now: i64) -> bool {
+        self.delegate_expiry != 0 && now > self.delegate_expiry
+    }
+
+    pub fn clear_delegate(&mut self) {
+        self.delegate = [0; 32];
+        self.delegate_destination = [0; 32];
+        self.delegate_allowance = 0;
+        self.delegate_expiry = 0;
+    }
+
+    /// Counts `amount` against the rate limit, starting a new period once the
+    /// current one has elapsed.
+    pub fn record_withdrawal(&mut self, amount: u64, now: i64) -> ProgramResult {
+        if self.withdraw_limit == 0 {
+            return Ok(());
+        }
+
+        if now >= self.period_start.saturating_add(self.limit_period) {
+            self.period_start = now;
+            self.withdrawn_in_period = 0;
+        }
+
+        let withdrawn = self
+            .withdrawn_in_period
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        if withdrawn > self.withdraw_limit {
+            return Err(MyProgramError::WithdrawLimitExceeded.into());
+        }
+
+        self.withdrawn_in_period = withdrawn;
+
+        Ok(())
+    }
+
+    /// Returns the address of `creator`'s vault named `label` and its canonical bump.
+    ///
+    /// The default vault lives at `[SEED, creator]`; labelled vaults use
+    /// `[LABELED_SEED, creator, label]` so a label can never reproduce the
+    /// `[SEED, owner, mint]` seeds of a token vault.
+    pub fn find_pda(creator: &Pubkey, label: &[u8; 32]) -> (Pubkey, u8) {
+        if *label == Self::DEFAULT_LABEL {
+            pubkey::find_program_address(&[Self::SEED.as_bytes(), creator], &crate::ID)
+        } else {
+            pubkey::find_program_address(
+                &[Self::LABELED_SEED.as_bytes(), creator, label],
+                &crate::ID,
+            )
+        }
+    }
+
+    /// Checks `pda` against the address derived from the stored creator, label and bump.
+    ///
+    /// The bump was verified canonical at init, so the curve check done by
+    /// `create_program_address` can be skipped.
+    pub fn validate_pda(&self, pda: &Pubkey) -> Result<(), ProgramError> {
+        let derived = if self.label == Self::DEFAULT_LABEL {
+            derive_address(
+                &[Self::SEED.as_bytes(), &self.creator],
+                Some(self.bump),
+                &crate::ID,
+            )
+        } else {
+            derive_address(
+                &[Self::LABELED_SEED.as_bytes(), &self.creator, &self.label],
+                Some(self.bump),
+                &crate::ID,
+            )
+        };
         if derived != *pda {
             return Err(MyProgramError::PdaMismatch.into());
         }
         Ok(())
     }
 
-    pub fn initialize(vault_acc: &AccountInfo, owner: &Pubkey) -> ProgramResult {
+    pub fn initialize(
+        vault_acc: &AccountInfo,
+        owner: &Pubkey,
+        label: &[u8; 32],
+        bump: u8,
+        unlock_timestamp: i64,
+        withdraw_cooldown: i64,
+    ) -> ProgramResult {
         let vault_state =
-            unsafe { load_acc_mut_unchecked::<VaultState>(vault_acc.borrow_mut_data_unchecked())? };
+            unsafe { load_acc_mut_uninit::<VaultState>(vault_acc.borrow_mut_data_unchecked())? };
 
-        vault_state.owner = *owner;
+        *vault_state = VaultState::new(*owner, *label, bump);
+        vault_state.unlock_timestamp = unlock_timestamp;
+        vault_state.withdraw_cooldown = withdraw_cooldown;
 
         Ok(())
     }
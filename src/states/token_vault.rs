@@ -0,0 +1,123 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+    ProgramResult,
+};
+
+use super::VaultState;
+use crate::cpi::token::{MINT_DECIMALS_OFFSET, MINT_LEN, TOKEN_ACCOUNT_LEN};
+use crate::errors::MyProgramError;
+use crate::events::VaultEvent;
+use crate::instructions::ProgramInstruction;
+
+/// A token account at the `[SEED, owner, mint]` PDA that is its own authority.
+pub struct TokenVaultAccount<'a> {
+    info: &'a AccountInfo,
+}
+
+impl<'a> TokenVaultAccount<'a> {
+    /// Returns the address of `owner`'s token vault for `mint` and its canonical bump.
+    pub fn find_pda(owner: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+        pubkey::find_program_address(&[VaultState::SEED.as_bytes(), owner, mint], &crate::ID)
+    }
+
+    pub fn validate_pda(
+        bump: u8,
+        pda: &Pubkey,
+        owner: &Pubkey,
+        mint: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        let seed_with_bump = &[VaultState::SEED.as_bytes(), owner, mint, &[bump]];
+        let derived = pubkey::create_program_address(seed_with_bump, &crate::ID)?;
+        if derived != *pda {
+            return Err(MyProgramError::PdaMismatch.into());
+        }
+        Ok(())
+    }
+
+    /// Loads the token vault of `owner` for `mint`, held by `token_program`.
+    pub fn load(
+        info: &'a AccountInfo,
+        owner: &Pubkey,
+        mint: &Pubkey,
+        token_program: &Pubkey,
+        bump: u8,
+    ) -> Result<Self, ProgramError> {
+        if !info.is_owned_by(token_program) {
+            return Err(if info.data_is_empty() {
+                MyProgramError::VaultNotInitialized.into()
+            } else {
+                MyProgramError::InvalidTokenAccount.into()
+            });
+        }
+        if !info.is_writable() {
+            return Err(MyProgramError::AccountNotWritable.into());
+        }
+        if info.data_len() < TOKEN_ACCOUNT_LEN {
+            return Err(MyProgramError::InvalidAccountLength.into());
+        }
+        Self::validate_pda(bump, info.key(), owner, mint)?;
+        Ok(Self { info })
+    }
+
+    /// Checks an account that is about to be created as the token vault of `owner`.
+    ///
+    /// Only the canonical bump is accepted, so each owner and mint get a single vault.
+    pub fn check_uninitialized(
+        info: &AccountInfo,
+        owner: &Pubkey,
+        mint: &Pubkey,
+        bump: u8,
+    ) -> ProgramResult {
+        if !info.is_writable() {
+            return Err(MyProgramError::AccountNotWritable.into());
+        }
+        if !info.data_is_empty() {
+            return Err(MyProgramError::VaultAlreadyInitialized.into());
+        }
+        let (pda, canonical_bump) = Self::find_pda(owner, mint);
+        if pda != *info.key() || bump != canonical_bump {
+            return Err(MyProgramError::PdaMismatch.into());
+        }
+        Ok(())
+    }
+
+    /// Logs `instruction`'s event with the vault's current token balance.
+    pub fn emit_event(
+        &self,
+        instruction: ProgramInstruction,
+        owner: &Pubkey,
+        amount: u64,
+        event_authority: Option<&AccountInfo>,
+    ) -> ProgramResult {
+        VaultEvent {
+            instruction,
+            vault: self.info.key(),
+            owner,
+            amount,
+            balance: self.amount(),
+        }
+        .emit(event_authority)
+    }
+
+    pub fn info(&self) -> &'a AccountInfo {
+        self.info
+    }
+
+    /// Reads the decimals of a mint owned by `token_program`.
+    pub fn mint_decimals(mint: &AccountInfo, token_program: &Pubkey) -> Result<u8, ProgramError> {
+        if !mint.is_owned_by(token_program) || mint.data_len() < MINT_LEN {
+            return Err(MyProgramError::InvalidMint.into());
+        }
+        Ok(unsafe { mint.borrow_data_unchecked() }[MINT_DECIMALS_OFFSET])
+    }
+
+    /// Token balance held by the vault.
+    pub fn amount(&self) -> u64 {
+        let data = unsafe { self.info.borrow_data_unchecked() };
+        let mut amount = [0u8; 8];
+        amount.copy_from_slice(&data[64..72]);
+        u64::from_le_bytes(amount)
+    }
+}
@@ -1,4 +1,8 @@
-use pinocchio::program_error::ProgramError;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::rent::{Rent, RENT_ID},
+};
 
 use crate::errors::MyProgramError;
 
@@ -6,22 +10,89 @@ pub trait DataLen {
     const LEN: usize;
 }
 
+/// Leading byte of every account owned by this program.
+///
+/// Indexers classify accounts by this byte, so values must never be reused.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AccountDiscriminator {
+    Uninitialized = 0,
+    Vault = 1,
+    MultisigVault = 2,
+}
+
+/// Account types that start with a `[discriminator, version]` header.
+pub trait Discriminator {
+    const DISCRIMINATOR: AccountDiscriminator;
+    const VERSION: u8;
+}
+
 #[inline(always)]
-pub unsafe fn load_acc_unchecked<T: DataLen>(bytes: &[u8]) -> Result<&T, ProgramError> {
+fn check_header<T: DataLen + Discriminator>(bytes: &[u8]) -> Result<(), ProgramError> {
     if bytes.len() != T::LEN {
-        return Err(ProgramError::InvalidAccountData);
+        return Err(MyProgramError::InvalidAccountLength.into());
+    }
+    if bytes[0] != T::DISCRIMINATOR as u8 {
+        return Err(MyProgramError::InvalidDiscriminator.into());
     }
+    if bytes[1] != T::VERSION {
+        return Err(MyProgramError::UnsupportedVersion.into());
+    }
+    Ok(())
+}
+
+/// # Safety
+///
+/// `bytes` must not be mutably borrowed elsewhere and must be suitably aligned for `T`.
+#[inline(always)]
+pub unsafe fn load_acc_unchecked<T: DataLen + Discriminator>(
+    bytes: &[u8],
+) -> Result<&T, ProgramError> {
+    check_header::<T>(bytes)?;
     Ok(&*(bytes.as_ptr() as *const T))
 }
 
+/// # Safety
+///
+/// `bytes` must not be borrowed elsewhere and must be suitably aligned for `T`.
 #[inline(always)]
-pub unsafe fn load_acc_mut_unchecked<T: DataLen>(bytes: &mut [u8]) -> Result<&mut T, ProgramError> {
+pub unsafe fn load_acc_mut_unchecked<T: DataLen + Discriminator>(
+    bytes: &mut [u8],
+) -> Result<&mut T, ProgramError> {
+    check_header::<T>(bytes)?;
+    Ok(&mut *(bytes.as_mut_ptr() as *mut T))
+}
+
+/// Loads freshly allocated account data for initialization.
+///
+/// # Safety
+///
+/// `bytes` must not be borrowed elsewhere and must be suitably aligned for `T`.
+#[inline(always)]
+pub unsafe fn load_acc_mut_uninit<T: DataLen + Discriminator>(
+    bytes: &mut [u8],
+) -> Result<&mut T, ProgramError> {
     if bytes.len() != T::LEN {
-        return Err(ProgramError::InvalidAccountData);
+        return Err(MyProgramError::InvalidAccountLength.into());
+    }
+    if bytes[0] != AccountDiscriminator::Uninitialized as u8 {
+        return Err(MyProgramError::VaultAlreadyInitialized.into());
     }
     Ok(&mut *(bytes.as_mut_ptr() as *mut T))
 }
 
+/// Reads rent from the rent sysvar account, rejecting any other account.
+#[inline(always)]
+pub fn load_rent(info: &AccountInfo) -> Result<Rent, ProgramError> {
+    if info.key() != &RENT_ID {
+        return Err(MyProgramError::InvalidRentSysvar.into());
+    }
+    Ok(*Rent::from_account_info(info)?)
+}
+
+/// # Safety
+///
+/// `bytes` must be suitably aligned for `T`.
 #[inline(always)]
 pub unsafe fn load_ix_data<T: DataLen>(bytes: &[u8]) -> Result<&T, ProgramError> {
     if bytes.len() != T::LEN {
@@ -30,10 +101,16 @@ pub unsafe fn load_ix_data<T: DataLen>(bytes: &[u8]) -> Result<&T, ProgramError>
     Ok(&*(bytes.as_ptr() as *const T))
 }
 
+/// # Safety
+///
+/// `T` must be a `#[repr(C)]` plain-data type.
 pub unsafe fn to_bytes<T: DataLen>(data: &T) -> &[u8] {
     core::slice::from_raw_parts(data as *const T as *const u8, T::LEN)
 }
 
+/// # Safety
+///
+/// `T` must be a `#[repr(C)]` plain-data type for which any bit pattern is valid.
 pub unsafe fn to_mut_bytes<T: DataLen>(data: &mut T) -> &mut [u8] {
     core::slice::from_raw_parts_mut(data as *mut T as *mut u8, T::LEN)
-}
\ No newline at end of file
+}
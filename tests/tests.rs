@@ -1,17 +1,24 @@
+#![allow(clippy::identity_op, clippy::let_and_return, clippy::useless_vec)]
+
 use mollusk_svm::result::{Check, ProgramResult};
 use mollusk_svm::{program, Mollusk};
 use solana_sdk::account::Account;
 use solana_sdk::instruction::{AccountMeta, Instruction};
 use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::program_error::ProgramError;
+use solana_sdk::program_option::COption;
 use solana_sdk::pubkey;
 use solana_sdk::pubkey::Pubkey;
 extern crate alloc;
 use alloc::vec;
 
+use pinocchio_vault::errors::MyProgramError;
 use pinocchio_vault::instructions::*;
-use pinocchio_vault::states::{to_bytes, DataLen as _, VaultState};
+use pinocchio_vault::states::{to_bytes, DataLen as _, MultisigVaultState, VaultState};
+use solana_sdk::program_pack::Pack as _;
 use solana_sdk::rent::Rent;
 use solana_sdk::sysvar::SysvarSerialize as _;
+use spl_token_interface::state::{Account as TokenAccount, AccountState, Mint};
 
 pub const PROGRAM: Pubkey = pubkey!("63vgRZotq9C4krvqWcVjWHgw1gaZTXuYu76sSbosq6ca");
 
@@ -19,6 +26,9 @@ pub const RENT: Pubkey = pubkey!("SysvarRent111111111111111111111111111111111");
 
 pub const PAYER: Pubkey = pubkey!("EcgxCCyx5YrFTN6WeQ9ioX6CGZVgWsbyXxzNSAZDzdVT");
 
+/// Label of a user's unnamed vault.
+pub const DEFAULT_LABEL: [u8; 32] = VaultState::DEFAULT_LABEL;
+
 pub fn mollusk() -> Mollusk {
     let mollusk = Mollusk::new(
         &PROGRAM,
@@ -52,7 +62,11 @@ fn test_close() {
         AccountMeta::new_readonly(RENT, false),
         AccountMeta::new_readonly(system_program, false),
     ];
-    let init_ix_data = Init { bump };
+    let init_ix_data = Init {
+        unlock_timestamp: 0,
+        withdraw_cooldown: 0,
+        label: DEFAULT_LABEL,
+    };
     let mut ser_init_ix_data = vec![0]; // discriminator for init
     ser_init_ix_data.extend_from_slice(unsafe { to_bytes(&init_ix_data) });
 
@@ -84,7 +98,6 @@ fn test_close() {
     ];
     let deposit_ix_data = Deposit {
         amount: deposit_amount,
-        bump,
     };
     let mut ser_deposit_ix_data = vec![1];
     ser_deposit_ix_data.extend_from_slice(unsafe { to_bytes(&deposit_ix_data) });
@@ -98,9 +111,7 @@ fn test_close() {
         &system_program,
     );
 
-    let vault_state = VaultState {
-        owner: PAYER.to_bytes(),
-    };
+    let vault_state = VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump);
     let mut vault_pre_deposit = Account::new(rent_exempt_lamports, VaultState::LEN, &PROGRAM);
     vault_pre_deposit.data = unsafe { to_bytes(&vault_state) }.to_vec();
 
@@ -123,7 +134,7 @@ fn test_close() {
         AccountMeta::new(vault_state_pda, false),
         AccountMeta::new_readonly(system_program, false),
     ];
-    let close_ix_data = Close { bump };
+    let close_ix_data = Close;
     let mut ser_close_ix_data = vec![3];
     ser_close_ix_data.extend_from_slice(unsafe { to_bytes(&close_ix_data) });
 
@@ -164,6 +175,20 @@ pub fn get_rent_data() -> Vec<u8> {
     }
 }
 
+pub fn rent_sysvar_account(mollusk: &Mollusk) -> Account {
+    let min_balance = mollusk.sysvars.rent.minimum_balance(Rent::size_of());
+    let mut rent_account = Account::new(min_balance, Rent::size_of(), &RENT);
+    rent_account.data = get_rent_data();
+    rent_account
+}
+
+/// Program-owned vault account holding `vault_state` and `lamports`.
+pub fn vault_account(vault_state: &VaultState, lamports: u64) -> Account {
+    let mut account = Account::new(lamports, VaultState::LEN, &PROGRAM);
+    account.data = unsafe { to_bytes(vault_state) }.to_vec();
+    account
+}
+
 #[test]
 fn test_initialize() {
     let mollusk = mollusk();
@@ -191,7 +216,11 @@ fn test_initialize() {
     ];
 
     // Create the instruction data
-    let ix_data = Init { bump };
+    let ix_data = Init {
+        unlock_timestamp: 0,
+        withdraw_cooldown: 0,
+        label: DEFAULT_LABEL,
+    };
 
     // Ix discriminator = 0
     let mut ser_ix_data = vec![0];
@@ -210,8 +239,17 @@ fn test_initialize() {
         (system_program, system_account.clone()),
     ];
 
-    let init_res =
-        mollusk.process_and_validate_instruction(&instruction, tx_accounts, &[Check::success()]);
+    // The canonical bump is derived on-chain and stored in the vault state
+    let init_res = mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[
+            Check::success(),
+            Check::account(&vault_state_pda)
+                .data(unsafe { to_bytes(&VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump)) })
+                .build(),
+        ],
+    );
 
     assert!(init_res.program_result == ProgramResult::Success);
 }
@@ -240,7 +278,11 @@ fn test_deposit() {
         AccountMeta::new_readonly(system_program, false),
     ];
 
-    let init_ix_data = Init { bump };
+    let init_ix_data = Init {
+        unlock_timestamp: 0,
+        withdraw_cooldown: 0,
+        label: DEFAULT_LABEL,
+    };
     let mut ser_init_ix_data = vec![0]; // discriminator for init
     ser_init_ix_data.extend_from_slice(unsafe { to_bytes(&init_ix_data) });
 
@@ -264,7 +306,6 @@ fn test_deposit() {
     let deposit_amount = 100_000_000; // 0.1 SOL in lamports
     let deposit_ix_data = Deposit {
         amount: deposit_amount,
-        bump,
     };
 
     let mut ser_deposit_ix_data = vec![1]; // discriminator for deposit
@@ -294,9 +335,7 @@ fn test_deposit() {
     );
 
     // Vault state data and account (post-init)
-    let vault_state = VaultState {
-        owner: PAYER.to_bytes(),
-    };
+    let vault_state = VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump);
     let mut vault_pre_deposit = Account::new(rent_exempt_lamports, VaultState::LEN, &PROGRAM);
     vault_pre_deposit.data = unsafe { to_bytes(&vault_state) }.to_vec();
 
@@ -340,7 +379,11 @@ fn test_withdraw() {
         AccountMeta::new_readonly(RENT, false),
         AccountMeta::new_readonly(system_program, false),
     ];
-    let init_ix_data = Init { bump };
+    let init_ix_data = Init {
+        unlock_timestamp: 0,
+        withdraw_cooldown: 0,
+        label: DEFAULT_LABEL,
+    };
     let mut ser_init_ix_data = vec![0]; // discriminator for init
     ser_init_ix_data.extend_from_slice(unsafe { to_bytes(&init_ix_data) });
 
@@ -372,7 +415,6 @@ fn test_withdraw() {
     ];
     let deposit_ix_data = Deposit {
         amount: deposit_amount,
-        bump,
     };
     let mut ser_deposit_ix_data = vec![1]; // discriminator for deposit
     ser_deposit_ix_data.extend_from_slice(unsafe { to_bytes(&deposit_ix_data) });
@@ -387,9 +429,7 @@ fn test_withdraw() {
         &system_program,
     );
 
-    let vault_state = VaultState {
-        owner: PAYER.to_bytes(),
-    };
+    let vault_state = VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump);
     let mut vault_pre_deposit = Account::new(rent_exempt_lamports, VaultState::LEN, &PROGRAM);
     vault_pre_deposit.data = unsafe { to_bytes(&vault_state) }.to_vec();
 
@@ -418,7 +458,6 @@ fn test_withdraw() {
     ];
     let withdraw_ix_data = Withdraw {
         amount: withdraw_amount,
-        bump,
     };
     let mut ser_withdraw_ix_data = vec![2]; // discriminator for withdraw
     ser_withdraw_ix_data.extend_from_slice(unsafe { to_bytes(&withdraw_ix_data) });
@@ -457,3 +496,3348 @@ fn test_withdraw() {
     );
     assert!(withdraw_res.program_result == ProgramResult::Success);
 }
+
+#[test]
+fn test_deposit_rejects_wrong_discriminator() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+    let payer_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    // Same length and owner as a vault, but tagged as uninitialized
+    let mut vault_state = VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump);
+    vault_state.discriminator = 0;
+    let mut vault_account = Account::new(rent_exempt_lamports, VaultState::LEN, &PROGRAM);
+    vault_account.data = unsafe { to_bytes(&vault_state) }.to_vec();
+
+    let deposit_ix_data = Deposit {
+        amount: 100_000_000,
+    };
+    let mut ser_deposit_ix_data = vec![1];
+    ser_deposit_ix_data.extend_from_slice(unsafe { to_bytes(&deposit_ix_data) });
+
+    let deposit_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_deposit_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_state_pda, false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+
+    mollusk.process_and_validate_instruction(
+        &deposit_instruction,
+        &[
+            (PAYER, payer_account),
+            (vault_state_pda, vault_account),
+            (system_program, system_account),
+        ],
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::InvalidDiscriminator as u32,
+        ))],
+    );
+}
+
+#[test]
+fn test_withdraw_rejects_vault_not_owned_by_program() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+
+    let min_balance = mollusk.sysvars.rent.minimum_balance(Rent::size_of());
+    let mut rent_account = Account::new(min_balance, Rent::size_of(), &RENT);
+    rent_account.data = get_rent_data();
+
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+    let payer_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    // A well-formed vault layout at the right address, but owned by another program
+    let vault_state = VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump);
+    let mut vault_account = Account::new(
+        rent_exempt_lamports + 200_000_000,
+        VaultState::LEN,
+        &Pubkey::new_unique(),
+    );
+    vault_account.data = unsafe { to_bytes(&vault_state) }.to_vec();
+
+    let withdraw_ix_data = Withdraw {
+        amount: 100_000_000,
+    };
+    let mut ser_withdraw_ix_data = vec![2];
+    ser_withdraw_ix_data.extend_from_slice(unsafe { to_bytes(&withdraw_ix_data) });
+
+    let withdraw_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_withdraw_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_state_pda, false),
+            AccountMeta::new_readonly(RENT, false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+
+    mollusk.process_and_validate_instruction(
+        &withdraw_instruction,
+        &[
+            (PAYER, payer_account),
+            (vault_state_pda, vault_account),
+            (RENT, rent_account),
+            (system_program, system_account),
+        ],
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::InvalidProgramOwner as u32,
+        ))],
+    );
+}
+
+#[test]
+fn test_token_vault_lifecycle() {
+    let mut mollusk = mollusk();
+    mollusk_svm_programs_token::token::add_program(&mut mollusk);
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (token_program, token_program_account) = mollusk_svm_programs_token::token::keyed_account();
+
+    let mint = Pubkey::new_unique();
+    let user_token = Pubkey::new_unique();
+    let (vault_pda, bump) = Pubkey::find_program_address(
+        &[
+            VaultState::SEED.as_bytes(),
+            &PAYER.to_bytes(),
+            &mint.to_bytes(),
+        ],
+        &PROGRAM,
+    );
+
+    let min_balance = mollusk.sysvars.rent.minimum_balance(Rent::size_of());
+    let mut rent_account = Account::new(min_balance, Rent::size_of(), &RENT);
+    rent_account.data = get_rent_data();
+
+    let mint_account = mollusk_svm_programs_token::token::create_account_for_mint(Mint {
+        mint_authority: COption::None,
+        supply: 1_000_000,
+        decimals: 6,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    });
+    let user_token_account =
+        mollusk_svm_programs_token::token::create_account_for_token_account(TokenAccount {
+            mint,
+            owner: PAYER,
+            amount: 1_000_000,
+            delegate: COption::None,
+            state: AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        });
+
+    // ---------- 1) Initialize ----------
+    let mut ser_init_ix_data = vec![4];
+    ser_init_ix_data.extend_from_slice(unsafe { to_bytes(&InitTokenVault { bump }) });
+    let init_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_init_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_pda, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(RENT, false),
+            AccountMeta::new_readonly(system_program, false),
+            AccountMeta::new_readonly(token_program, false),
+        ],
+    );
+
+    // ---------- 2) Deposit ----------
+    let deposit_ix_data = DepositToken {
+        amount: 400_000,
+        bump,
+    };
+    let mut ser_deposit_ix_data = vec![5];
+    ser_deposit_ix_data.extend_from_slice(unsafe { to_bytes(&deposit_ix_data) });
+    let deposit_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_deposit_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(user_token, false),
+            AccountMeta::new(vault_pda, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(token_program, false),
+        ],
+    );
+
+    // ---------- 3) Withdraw ----------
+    let withdraw_ix_data = WithdrawToken {
+        amount: 150_000,
+        bump,
+    };
+    let mut ser_withdraw_ix_data = vec![6];
+    ser_withdraw_ix_data.extend_from_slice(unsafe { to_bytes(&withdraw_ix_data) });
+    let withdraw_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_withdraw_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_pda, false),
+            AccountMeta::new(user_token, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(token_program, false),
+        ],
+    );
+
+    // ---------- 4) Close ----------
+    let mut ser_close_ix_data = vec![7];
+    ser_close_ix_data.extend_from_slice(unsafe { to_bytes(&CloseTokenVault { bump }) });
+    let close_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_close_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_pda, false),
+            AccountMeta::new(user_token, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(token_program, false),
+        ],
+    );
+
+    let vault_after_deposit = 400_000u64.to_le_bytes();
+    let vault_after_withdraw = 250_000u64.to_le_bytes();
+    let user_after_close = 1_000_000u64.to_le_bytes();
+
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (
+                &init_instruction,
+                &[
+                    Check::success(),
+                    Check::account(&vault_pda).owner(&token_program).build(),
+                ],
+            ),
+            (
+                &deposit_instruction,
+                &[
+                    Check::success(),
+                    Check::account(&vault_pda)
+                        .data_slice(64, &vault_after_deposit)
+                        .build(),
+                ],
+            ),
+            (
+                &withdraw_instruction,
+                &[
+                    Check::success(),
+                    Check::account(&vault_pda)
+                        .data_slice(64, &vault_after_withdraw)
+                        .build(),
+                ],
+            ),
+            (
+                &close_instruction,
+                &[
+                    Check::success(),
+                    Check::account(&vault_pda).closed().build(),
+                    Check::account(&user_token)
+                        .data_slice(64, &user_after_close)
+                        .build(),
+                    Check::account(&PAYER).lamports(LAMPORTS_PER_SOL).build(),
+                ],
+            ),
+        ],
+        &[
+            (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (vault_pda, Account::new(0, 0, &system_program)),
+            (mint, mint_account),
+            (user_token, user_token_account),
+            (RENT, rent_account),
+            (system_program, system_account),
+            (token_program, token_program_account),
+        ],
+    );
+}
+
+/// Token-2022 mint with a `TransferFeeConfig` extension charging `basis_points`.
+fn transfer_fee_mint_account(decimals: u8, basis_points: u16) -> Account {
+    let mut data = vec![0u8; 165];
+    Mint {
+        mint_authority: COption::None,
+        supply: 1_000_000,
+        decimals,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut data[..Mint::LEN]);
+    data.push(1); // AccountType::Mint
+
+    // TransferFeeConfig: two authorities, withheld amount, older and newer fee
+    data.extend_from_slice(&1u16.to_le_bytes());
+    data.extend_from_slice(&108u16.to_le_bytes());
+    data.extend_from_slice(&[0u8; 32 + 32 + 8]);
+    for _ in 0..2 {
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.extend_from_slice(&u64::MAX.to_le_bytes());
+        data.extend_from_slice(&basis_points.to_le_bytes());
+    }
+
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: mollusk_svm_programs_token::token2022::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Token-2022 account for a transfer-fee mint, carrying a `TransferFeeAmount` extension.
+fn transfer_fee_token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> Account {
+    let mut data = vec![0u8; TokenAccount::LEN];
+    TokenAccount {
+        mint,
+        owner,
+        amount,
+        delegate: COption::None,
+        state: AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    data.push(2); // AccountType::Account
+    data.extend_from_slice(&2u16.to_le_bytes());
+    data.extend_from_slice(&8u16.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: mollusk_svm_programs_token::token2022::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+#[test]
+fn test_token_2022_vault_with_transfer_fee() {
+    let mut mollusk = mollusk();
+    mollusk_svm_programs_token::token2022::add_program(&mut mollusk);
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (token_program, token_program_account) =
+        mollusk_svm_programs_token::token2022::keyed_account();
+
+    let mint = Pubkey::new_unique();
+    let user_token = Pubkey::new_unique();
+    let (vault_pda, bump) = Pubkey::find_program_address(
+        &[
+            VaultState::SEED.as_bytes(),
+            &PAYER.to_bytes(),
+            &mint.to_bytes(),
+        ],
+        &PROGRAM,
+    );
+
+    let min_balance = mollusk.sysvars.rent.minimum_balance(Rent::size_of());
+    let mut rent_account = Account::new(min_balance, Rent::size_of(), &RENT);
+    rent_account.data = get_rent_data();
+
+    // ---------- 1) Initialize ----------
+    let mut ser_init_ix_data = vec![4];
+    ser_init_ix_data.extend_from_slice(unsafe { to_bytes(&InitTokenVault { bump }) });
+    let init_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_init_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_pda, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(RENT, false),
+            AccountMeta::new_readonly(system_program, false),
+            AccountMeta::new_readonly(token_program, false),
+        ],
+    );
+
+    // ---------- 2) Deposit with a 1% fee ----------
+    let deposit_ix_data = DepositToken {
+        amount: 100_000,
+        bump,
+    };
+    let mut ser_deposit_ix_data = vec![5];
+    ser_deposit_ix_data.extend_from_slice(unsafe { to_bytes(&deposit_ix_data) });
+    let deposit_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_deposit_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(user_token, false),
+            AccountMeta::new(vault_pda, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(token_program, false),
+        ],
+    );
+
+    // ---------- 3) Close, harvesting the withheld fee ----------
+    let mut ser_close_ix_data = vec![7];
+    ser_close_ix_data.extend_from_slice(unsafe { to_bytes(&CloseTokenVault { bump }) });
+    let close_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_close_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_pda, false),
+            AccountMeta::new(user_token, false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new_readonly(token_program, false),
+        ],
+    );
+
+    let received = 99_000u64.to_le_bytes();
+
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (
+                &init_instruction,
+                &[
+                    Check::success(),
+                    Check::account(&vault_pda).owner(&token_program).build(),
+                ],
+            ),
+            (
+                &deposit_instruction,
+                &[
+                    Check::success(),
+                    Check::return_data(&received),
+                    Check::account(&vault_pda).data_slice(64, &received).build(),
+                ],
+            ),
+            (
+                &close_instruction,
+                &[
+                    Check::success(),
+                    Check::account(&vault_pda).closed().build(),
+                ],
+            ),
+        ],
+        &[
+            (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (vault_pda, Account::new(0, 0, &system_program)),
+            (mint, transfer_fee_mint_account(6, 100)),
+            (
+                user_token,
+                transfer_fee_token_account(mint, PAYER, 1_000_000),
+            ),
+            (RENT, rent_account),
+            (system_program, system_account),
+            (token_program, token_program_account),
+        ],
+    );
+}
+
+/// A minimal sBPF program that returns success, standing in for a transfer-hook program.
+fn noop_program_elf() -> Vec<u8> {
+    const EHDR_LEN: usize = 64;
+    // mov64 r0, 0; exit
+    const TEXT: [u8; 16] = [0xb7, 0, 0, 0, 0, 0, 0, 0, 0x95, 0, 0, 0, 0, 0, 0, 0];
+    const SHSTRTAB: &[u8] = b"\0.text\0.shstrtab\0";
+    let text_off = EHDR_LEN;
+    let strtab_off = text_off + TEXT.len();
+    let shdr_off = (strtab_off + SHSTRTAB.len() + 7) & !7;
+
+    let mut elf = vec![0u8; shdr_off];
+    elf[..4].copy_from_slice(b"\x7fELF");
+    elf[4] = 2; // 64-bit
+    elf[5] = 1; // little endian
+    elf[6] = 1; // ELF version
+    elf[16..18].copy_from_slice(&3u16.to_le_bytes()); // ET_DYN
+    elf[18..20].copy_from_slice(&247u16.to_le_bytes()); // EM_BPF
+    elf[20..24].copy_from_slice(&1u32.to_le_bytes());
+    elf[24..32].copy_from_slice(&(text_off as u64).to_le_bytes()); // e_entry
+    elf[40..48].copy_from_slice(&(shdr_off as u64).to_le_bytes()); // e_shoff
+    elf[52..54].copy_from_slice(&(EHDR_LEN as u16).to_le_bytes());
+    elf[54..56].copy_from_slice(&56u16.to_le_bytes());
+    elf[58..60].copy_from_slice(&64u16.to_le_bytes());
+    elf[60..62].copy_from_slice(&3u16.to_le_bytes()); // e_shnum
+    elf[62..64].copy_from_slice(&2u16.to_le_bytes()); // e_shstrndx
+    elf[text_off..strtab_off].copy_from_slice(&TEXT);
+    elf[strtab_off..strtab_off + SHSTRTAB.len()].copy_from_slice(SHSTRTAB);
+
+    let section = |name: u32, kind: u32, flags: u64, offset: usize, size: usize| {
+        let mut header = [0u8; 64];
+        header[0..4].copy_from_slice(&name.to_le_bytes());
+        header[4..8].copy_from_slice(&kind.to_le_bytes());
+        header[8..16].copy_from_slice(&flags.to_le_bytes());
+        let addr = if flags != 0 { offset as u64 } else { 0 };
+        header[16..24].copy_from_slice(&addr.to_le_bytes());
+        header[24..32].copy_from_slice(&(offset as u64).to_le_bytes());
+        header[32..40].copy_from_slice(&(size as u64).to_le_bytes());
+        header[48..56].copy_from_slice(&1u64.to_le_bytes());
+        header
+    };
+    elf.extend_from_slice(&[0u8; 64]);
+    elf.extend_from_slice(&section(1, 1, 0x6, text_off, TEXT.len())); // PROGBITS, ALLOC | EXEC
+    elf.extend_from_slice(&section(7, 3, 0, strtab_off, SHSTRTAB.len())); // STRTAB
+    elf
+}
+
+/// Token-2022 mint with a `TransferHook` extension pointing at `hook_program`.
+fn transfer_hook_mint_account(decimals: u8, hook_program: Pubkey) -> Account {
+    let mut data = vec![0u8; 165];
+    Mint {
+        mint_authority: COption::None,
+        supply: 1_000_000,
+        decimals,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut data[..Mint::LEN]);
+    data.push(1); // AccountType::Mint
+
+    // TransferHook: authority and program id
+    data.extend_from_slice(&14u16.to_le_bytes());
+    data.extend_from_slice(&64u16.to_le_bytes());
+    data.extend_from_slice(&[0u8; 32]);
+    data.extend_from_slice(&hook_program.to_bytes());
+
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: mollusk_svm_programs_token::token2022::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Token-2022 account for a transfer-hook mint, carrying a `TransferHookAccount` extension.
+fn transfer_hook_token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> Account {
+    let mut data = vec![0u8; TokenAccount::LEN];
+    TokenAccount {
+        mint,
+        owner,
+        amount,
+        delegate: COption::None,
+        state: AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    data.push(2); // AccountType::Account
+    data.extend_from_slice(&15u16.to_le_bytes());
+    data.extend_from_slice(&1u16.to_le_bytes());
+    data.push(0); // not transferring
+
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: mollusk_svm_programs_token::token2022::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Hook validation account listing `extra` as the only account the hook needs.
+fn extra_account_metas_account(hook_program: Pubkey, extra: Pubkey) -> Account {
+    // `Execute` instruction discriminator
+    let mut data = vec![105, 37, 101, 197, 75, 251, 102, 26];
+    data.extend_from_slice(&(4u32 + 35).to_le_bytes());
+    data.extend_from_slice(&1u32.to_le_bytes());
+    data.push(0); // fixed address
+    data.extend_from_slice(&extra.to_bytes());
+    data.push(0); // not a signer
+    data.push(0); // not writable
+
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: hook_program,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+#[test]
+fn test_token_2022_deposit_forwards_transfer_hook_accounts() {
+    let mut mollusk = mollusk();
+    mollusk_svm_programs_token::token2022::add_program(&mut mollusk);
+
+    let hook_program = Pubkey::new_unique();
+    let hook_elf = noop_program_elf();
+    mollusk.add_program_with_elf_and_loader(
+        &hook_program,
+        &hook_elf,
+        &mollusk_svm::program::loader_keys::LOADER_V2,
+    );
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (token_program, token_program_account) =
+        mollusk_svm_programs_token::token2022::keyed_account();
+
+    let mint = Pubkey::new_unique();
+    let user_token = Pubkey::new_unique();
+    let hook_extra = Pubkey::new_unique();
+    let (vault_pda, bump) = Pubkey::find_program_address(
+        &[
+            VaultState::SEED.as_bytes(),
+            &PAYER.to_bytes(),
+            &mint.to_bytes(),
+        ],
+        &PROGRAM,
+    );
+    let (validation, _) =
+        Pubkey::find_program_address(&[b"extra-account-metas", &mint.to_bytes()], &hook_program);
+
+    let mut ser_deposit_ix_data = vec![5];
+    ser_deposit_ix_data.extend_from_slice(unsafe {
+        to_bytes(&DepositToken {
+            amount: 100_000,
+            bump,
+        })
+    });
+    let deposit_instruction = |extra_accounts: &[Pubkey]| {
+        let mut metas = vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(user_token, false),
+            AccountMeta::new(vault_pda, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(token_program, false),
+        ];
+        metas.extend(
+            extra_accounts
+                .iter()
+                .map(|key| AccountMeta::new_readonly(*key, false)),
+        );
+        Instruction::new_with_bytes(PROGRAM, &ser_deposit_ix_data, metas)
+    };
+
+    let accounts = [
+        (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (
+            user_token,
+            transfer_hook_token_account(mint, PAYER, 1_000_000),
+        ),
+        (vault_pda, transfer_hook_token_account(mint, vault_pda, 0)),
+        (mint, transfer_hook_mint_account(6, hook_program)),
+        (token_program, token_program_account),
+        (
+            hook_program,
+            program::create_program_account_loader_v2(&hook_elf),
+        ),
+        (
+            validation,
+            extra_account_metas_account(hook_program, hook_extra),
+        ),
+        (
+            hook_extra,
+            Account::new(LAMPORTS_PER_SOL, 0, &system_program),
+        ),
+        (system_program, system_account),
+    ];
+
+    let deposited = 100_000u64.to_le_bytes();
+    mollusk.process_and_validate_instruction(
+        &deposit_instruction(&[hook_program, validation, hook_extra]),
+        &accounts,
+        &[
+            Check::success(),
+            Check::return_data(&deposited),
+            Check::account(&vault_pda)
+                .data_slice(64, &deposited)
+                .build(),
+        ],
+    );
+
+    // Without the account named in the validation data the hook cannot be resolved.
+    let result =
+        mollusk.process_instruction(&deposit_instruction(&[hook_program, validation]), &accounts);
+    assert!(result.program_result.is_err());
+}
+
+#[test]
+fn test_withdraw_rejects_locked_vault() {
+    let mut mollusk = mollusk();
+    mollusk.sysvars.clock.unix_timestamp = 1_000;
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+
+    let mut vault_state = VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump);
+    vault_state.unlock_timestamp = 2_000;
+
+    let withdraw_ix_data = Withdraw {
+        amount: 100_000_000,
+    };
+    let mut ser_withdraw_ix_data = vec![2];
+    ser_withdraw_ix_data.extend_from_slice(unsafe { to_bytes(&withdraw_ix_data) });
+
+    let withdraw_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_withdraw_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_state_pda, false),
+            AccountMeta::new_readonly(RENT, false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+
+    let withdraw_tx_accounts = [
+        (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (
+            vault_state_pda,
+            vault_account(&vault_state, rent_exempt_lamports + 200_000_000),
+        ),
+        (RENT, rent_sysvar_account(&mollusk)),
+        (system_program, system_account),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &withdraw_instruction,
+        &withdraw_tx_accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::VaultLocked as u32,
+        ))],
+    );
+
+    // Once the clock passes the unlock timestamp the same withdrawal succeeds
+    mollusk.sysvars.clock.unix_timestamp = 2_000;
+    mollusk.process_and_validate_instruction(
+        &withdraw_instruction,
+        &withdraw_tx_accounts,
+        &[Check::success()],
+    );
+}
+
+#[test]
+fn test_set_lock_only_extends() {
+    let mollusk = mollusk();
+
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+
+    let mut vault_state = VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump);
+    vault_state.unlock_timestamp = 1_000;
+
+    let set_lock_instruction = |unlock_timestamp: i64| {
+        let mut ser_ix_data = vec![8];
+        ser_ix_data.extend_from_slice(unsafe { to_bytes(&SetLock { unlock_timestamp }) });
+        Instruction::new_with_bytes(
+            PROGRAM,
+            &ser_ix_data,
+            vec![
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new(vault_state_pda, false),
+            ],
+        )
+    };
+
+    let tx_accounts = [
+        (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &Pubkey::default())),
+        (
+            vault_state_pda,
+            vault_account(&vault_state, rent_exempt_lamports),
+        ),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &set_lock_instruction(500),
+        &tx_accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::LockShortened as u32,
+        ))],
+    );
+
+    let mut extended_state = vault_state;
+    extended_state.unlock_timestamp = 5_000;
+    mollusk.process_and_validate_instruction(
+        &set_lock_instruction(5_000),
+        &tx_accounts,
+        &[
+            Check::success(),
+            Check::account(&vault_state_pda)
+                .data(unsafe { to_bytes(&extended_state) })
+                .build(),
+        ],
+    );
+}
+
+fn withdraw_request_instructions(
+    vault_state_pda: Pubkey,
+    amount: u64,
+) -> (Instruction, Instruction, Instruction) {
+    let mut ser_request_ix_data = vec![9];
+    ser_request_ix_data.extend_from_slice(unsafe { to_bytes(&Withdraw { amount }) });
+    let request_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_request_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_state_pda, false),
+        ],
+    );
+
+    let mut ser_execute_ix_data = vec![10];
+    ser_execute_ix_data.extend_from_slice(unsafe { to_bytes(&ExecuteWithdraw) });
+    let execute_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_execute_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_state_pda, false),
+            AccountMeta::new_readonly(RENT, false),
+            AccountMeta::new_readonly(Pubkey::default(), false),
+        ],
+    );
+
+    let mut ser_cancel_ix_data = vec![11];
+    ser_cancel_ix_data.extend_from_slice(unsafe { to_bytes(&CancelWithdraw) });
+    let cancel_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_cancel_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_state_pda, false),
+        ],
+    );
+
+    (request_instruction, execute_instruction, cancel_instruction)
+}
+
+#[test]
+fn test_withdraw_request_waits_for_cooldown() {
+    let mut mollusk = mollusk();
+    mollusk.sysvars.clock.unix_timestamp = 1_000;
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+    let withdraw_amount = 100_000_000;
+
+    let mut vault_state = VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump);
+    vault_state.withdraw_cooldown = 100;
+
+    let tx_accounts = [
+        (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (
+            vault_state_pda,
+            vault_account(&vault_state, rent_exempt_lamports + 200_000_000),
+        ),
+        (RENT, rent_sysvar_account(&mollusk)),
+        (system_program, system_account),
+    ];
+
+    // Instant withdrawals are disabled once a cooldown is configured
+    let mut ser_withdraw_ix_data = vec![2];
+    ser_withdraw_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Withdraw {
+            amount: withdraw_amount,
+        })
+    });
+    let withdraw_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_withdraw_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_state_pda, false),
+            AccountMeta::new_readonly(RENT, false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+    mollusk.process_and_validate_instruction(
+        &withdraw_instruction,
+        &tx_accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::WithdrawRequestRequired as u32,
+        ))],
+    );
+
+    let (request_instruction, execute_instruction, _) =
+        withdraw_request_instructions(vault_state_pda, withdraw_amount);
+
+    let request_res = mollusk.process_and_validate_instruction(
+        &request_instruction,
+        &tx_accounts,
+        &[Check::success()],
+    );
+
+    // Still inside the cooldown window
+    mollusk.process_and_validate_instruction(
+        &execute_instruction,
+        &request_res.resulting_accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::CooldownActive as u32,
+        ))],
+    );
+
+    mollusk.sysvars.clock.unix_timestamp = 1_100;
+    mollusk.process_and_validate_instruction(
+        &execute_instruction,
+        &request_res.resulting_accounts,
+        &[
+            Check::success(),
+            Check::account(&vault_state_pda)
+                .lamports(rent_exempt_lamports + 200_000_000 - withdraw_amount)
+                .build(),
+            Check::account(&PAYER)
+                .lamports(LAMPORTS_PER_SOL + withdraw_amount)
+                .build(),
+        ],
+    );
+}
+
+#[test]
+fn test_cancelled_withdraw_request_cannot_execute() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+
+    let (request_instruction, execute_instruction, cancel_instruction) =
+        withdraw_request_instructions(vault_state_pda, 100_000_000);
+
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (&request_instruction, &[Check::success()]),
+            (&cancel_instruction, &[Check::success()]),
+            (
+                &execute_instruction,
+                &[Check::err(ProgramError::Custom(
+                    MyProgramError::NoPendingWithdrawal as u32,
+                ))],
+            ),
+        ],
+        &[
+            (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (
+                vault_state_pda,
+                vault_account(
+                    &VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump),
+                    rent_exempt_lamports + 200_000_000,
+                ),
+            ),
+            (RENT, rent_sysvar_account(&mollusk)),
+            (system_program, system_account),
+        ],
+    );
+}
+
+#[test]
+fn test_set_cooldown_can_only_be_lengthened() {
+    let mollusk = mollusk();
+
+    let (system_program, _) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+
+    let mut vault_state = VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump);
+    vault_state.withdraw_cooldown = 3_600;
+
+    let set_cooldown_instruction = |withdraw_cooldown: i64| {
+        let mut ser_ix_data = vec![12];
+        ser_ix_data.extend_from_slice(unsafe { to_bytes(&SetCooldown { withdraw_cooldown }) });
+        Instruction::new_with_bytes(
+            PROGRAM,
+            &ser_ix_data,
+            vec![
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new(vault_state_pda, false),
+            ],
+        )
+    };
+
+    let accounts = [
+        (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (
+            vault_state_pda,
+            vault_account(&vault_state, rent_exempt_lamports),
+        ),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &set_cooldown_instruction(60),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::CooldownShortened as u32,
+        ))],
+    );
+
+    let mut lengthened = vault_state;
+    lengthened.withdraw_cooldown = 7_200;
+    mollusk.process_and_validate_instruction(
+        &set_cooldown_instruction(7_200),
+        &accounts,
+        &[
+            Check::success(),
+            Check::account(&vault_state_pda)
+                .data(unsafe { to_bytes(&lengthened) })
+                .build(),
+        ],
+    );
+}
+
+#[test]
+fn test_close_with_cooldown_only_recovers_rent_reserve() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+
+    let mut vault_state = VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump);
+    vault_state.withdraw_cooldown = 3_600;
+
+    let mut ser_close_ix_data = vec![3];
+    ser_close_ix_data.extend_from_slice(unsafe { to_bytes(&Close) });
+    let close_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_close_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_state_pda, false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+
+    // Funds above the rent reserve must leave through a withdrawal request
+    mollusk.process_and_validate_instruction(
+        &close_instruction,
+        &[
+            (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (
+                vault_state_pda,
+                vault_account(&vault_state, rent_exempt_lamports + 200_000_000),
+            ),
+            (system_program, system_account.clone()),
+        ],
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::WithdrawRequestRequired as u32,
+        ))],
+    );
+
+    mollusk.process_and_validate_instruction(
+        &close_instruction,
+        &[
+            (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (
+                vault_state_pda,
+                vault_account(&vault_state, rent_exempt_lamports),
+            ),
+            (system_program, system_account),
+        ],
+        &[
+            Check::success(),
+            Check::account(&vault_state_pda).closed().build(),
+            Check::account(&PAYER)
+                .lamports(LAMPORTS_PER_SOL + rent_exempt_lamports)
+                .build(),
+        ],
+    );
+}
+
+#[test]
+fn test_withdraw_rate_limit_resets_each_period() {
+    let mut mollusk = mollusk();
+    mollusk.sysvars.clock.unix_timestamp = 1_000;
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+
+    let mut vault_state = VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump);
+    vault_state.withdraw_limit = 150_000_000;
+    vault_state.limit_period = 86_400;
+
+    let mut ser_withdraw_ix_data = vec![2];
+    ser_withdraw_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Withdraw {
+            amount: 100_000_000,
+        })
+    });
+    let withdraw_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_withdraw_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_state_pda, false),
+            AccountMeta::new_readonly(RENT, false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+
+    let first_res = mollusk.process_and_validate_instruction(
+        &withdraw_instruction,
+        &[
+            (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (
+                vault_state_pda,
+                vault_account(&vault_state, rent_exempt_lamports + LAMPORTS_PER_SOL),
+            ),
+            (RENT, rent_sysvar_account(&mollusk)),
+            (system_program, system_account),
+        ],
+        &[Check::success()],
+    );
+
+    // 200_000_000 within the same day exceeds the 150_000_000 cap
+    mollusk.process_and_validate_instruction(
+        &withdraw_instruction,
+        &first_res.resulting_accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::WithdrawLimitExceeded as u32,
+        ))],
+    );
+
+    mollusk.sysvars.clock.unix_timestamp = 1_000 + 86_400;
+    mollusk.process_and_validate_instruction(
+        &withdraw_instruction,
+        &first_res.resulting_accounts,
+        &[Check::success()],
+    );
+
+    // Raising the cap is rejected once a limit is in place
+    let mut ser_limit_ix_data = vec![13];
+    ser_limit_ix_data.extend_from_slice(unsafe {
+        to_bytes(&SetWithdrawLimit {
+            withdraw_limit: 300_000_000,
+            limit_period: 86_400,
+        })
+    });
+    mollusk.process_and_validate_instruction(
+        &Instruction::new_with_bytes(
+            PROGRAM,
+            &ser_limit_ix_data,
+            vec![
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new(vault_state_pda, false),
+            ],
+        ),
+        &first_res.resulting_accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::WithdrawLimitLoosened as u32,
+        ))],
+    );
+}
+
+#[test]
+fn test_delegate_withdraw_spends_allowance() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+
+    let delegate = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+
+    let mut ser_approve_ix_data = vec![14];
+    ser_approve_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Approve {
+            allowance: 150_000_000,
+            expiry: 0,
+        })
+    });
+    let approve_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_approve_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_state_pda, false),
+            AccountMeta::new_readonly(delegate, false),
+            AccountMeta::new_readonly(destination, false),
+        ],
+    );
+
+    let mut ser_withdraw_ix_data = vec![16];
+    ser_withdraw_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Withdraw {
+            amount: 100_000_000,
+        })
+    });
+    let delegate_withdraw_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_withdraw_ix_data,
+        vec![
+            AccountMeta::new_readonly(delegate, true),
+            AccountMeta::new(vault_state_pda, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(RENT, false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+
+    let accounts = [
+        (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (
+            vault_state_pda,
+            vault_account(
+                &VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump),
+                rent_exempt_lamports + LAMPORTS_PER_SOL,
+            ),
+        ),
+        (delegate, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (destination, Account::new(0, 0, &system_program)),
+        (RENT, rent_sysvar_account(&mollusk)),
+        (system_program, system_account),
+    ];
+
+    // The second withdrawal would take the delegate past its allowance
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (&approve_instruction, &[Check::success()]),
+            (
+                &delegate_withdraw_instruction,
+                &[
+                    Check::success(),
+                    Check::account(&destination).lamports(100_000_000).build(),
+                ],
+            ),
+            (
+                &delegate_withdraw_instruction,
+                &[Check::err(ProgramError::Custom(
+                    MyProgramError::AllowanceExceeded as u32,
+                ))],
+            ),
+        ],
+        &accounts,
+    );
+}
+
+/// Vault of `PAYER` with `delegate` approved to withdraw to `destination`.
+fn delegated_vault_state(bump: u8, delegate: Pubkey, destination: Pubkey) -> VaultState {
+    let mut vault_state = VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump);
+    vault_state.delegate = delegate.to_bytes();
+    vault_state.delegate_destination = destination.to_bytes();
+    vault_state.delegate_allowance = 150_000_000;
+    vault_state
+}
+
+#[test]
+fn test_revoke_removes_delegate() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+
+    let delegate = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+    let vault_state = delegated_vault_state(bump, delegate, destination);
+
+    let mut ser_revoke_ix_data = vec![15];
+    ser_revoke_ix_data.extend_from_slice(unsafe { to_bytes(&Revoke) });
+    let revoke_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_revoke_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_state_pda, false),
+        ],
+    );
+
+    let mut ser_withdraw_ix_data = vec![16];
+    ser_withdraw_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Withdraw {
+            amount: 100_000_000,
+        })
+    });
+    let delegate_withdraw_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_withdraw_ix_data,
+        vec![
+            AccountMeta::new_readonly(delegate, true),
+            AccountMeta::new(vault_state_pda, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(RENT, false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+
+    let revoked_state = VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump);
+
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (
+                &revoke_instruction,
+                &[
+                    Check::success(),
+                    Check::account(&vault_state_pda)
+                        .data(unsafe { to_bytes(&revoked_state) })
+                        .build(),
+                ],
+            ),
+            (
+                &delegate_withdraw_instruction,
+                &[Check::err(ProgramError::Custom(
+                    MyProgramError::InvalidDelegate as u32,
+                ))],
+            ),
+        ],
+        &[
+            (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (
+                vault_state_pda,
+                vault_account(&vault_state, rent_exempt_lamports + LAMPORTS_PER_SOL),
+            ),
+            (delegate, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (destination, Account::new(0, 0, &system_program)),
+            (RENT, rent_sysvar_account(&mollusk)),
+            (system_program, system_account),
+        ],
+    );
+}
+
+#[test]
+fn test_delegate_withdraw_rejects_expired_delegation() {
+    let mut mollusk = mollusk();
+    mollusk.sysvars.clock.unix_timestamp = 1_000;
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+
+    let delegate = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+    let mut vault_state = delegated_vault_state(bump, delegate, destination);
+    vault_state.delegate_expiry = 999;
+
+    let mut ser_withdraw_ix_data = vec![16];
+    ser_withdraw_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Withdraw {
+            amount: 100_000_000,
+        })
+    });
+
+    mollusk.process_and_validate_instruction(
+        &Instruction::new_with_bytes(
+            PROGRAM,
+            &ser_withdraw_ix_data,
+            vec![
+                AccountMeta::new_readonly(delegate, true),
+                AccountMeta::new(vault_state_pda, false),
+                AccountMeta::new(destination, false),
+                AccountMeta::new_readonly(RENT, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        ),
+        &[
+            (
+                vault_state_pda,
+                vault_account(&vault_state, rent_exempt_lamports + LAMPORTS_PER_SOL),
+            ),
+            (delegate, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (destination, Account::new(0, 0, &system_program)),
+            (RENT, rent_sysvar_account(&mollusk)),
+            (system_program, system_account),
+        ],
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::DelegationExpired as u32,
+        ))],
+    );
+}
+
+#[test]
+fn test_approve_rejects_vault_and_zero_destination() {
+    let mollusk = mollusk();
+
+    let (system_program, _) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+
+    let delegate = Pubkey::new_unique();
+    let zero = Pubkey::default();
+
+    let mut ser_approve_ix_data = vec![14];
+    ser_approve_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Approve {
+            allowance: 150_000_000,
+            expiry: 0,
+        })
+    });
+
+    for destination in [vault_state_pda, zero] {
+        mollusk.process_and_validate_instruction(
+            &Instruction::new_with_bytes(
+                PROGRAM,
+                &ser_approve_ix_data,
+                vec![
+                    AccountMeta::new(PAYER, true),
+                    AccountMeta::new(vault_state_pda, false),
+                    AccountMeta::new_readonly(delegate, false),
+                    AccountMeta::new_readonly(destination, false),
+                ],
+            ),
+            &[
+                (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+                (
+                    vault_state_pda,
+                    vault_account(
+                        &VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump),
+                        rent_exempt_lamports,
+                    ),
+                ),
+                (delegate, Account::new(0, 0, &system_program)),
+                (zero, Account::new(0, 0, &system_program)),
+            ],
+            &[Check::err(ProgramError::Custom(
+                MyProgramError::InvalidDestination as u32,
+            ))],
+        );
+    }
+}
+
+#[test]
+fn test_multisig_withdraw_requires_threshold() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let seed = [7u8; 32];
+    let (multisig_pda, _) = Pubkey::find_program_address(
+        &[
+            MultisigVaultState::SEED.as_bytes(),
+            &PAYER.to_bytes(),
+            &seed,
+        ],
+        &PROGRAM,
+    );
+    let members = [
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+    let recipient = Pubkey::new_unique();
+
+    let mut ser_init_ix_data = vec![17];
+    ser_init_ix_data.extend_from_slice(unsafe { to_bytes(&InitMultisig { seed, threshold: 2 }) });
+    let mut init_accounts = vec![
+        AccountMeta::new(PAYER, true),
+        AccountMeta::new(multisig_pda, false),
+        AccountMeta::new_readonly(RENT, false),
+        AccountMeta::new_readonly(system_program, false),
+    ];
+    init_accounts.extend(members.iter().map(|m| AccountMeta::new_readonly(*m, false)));
+
+    let mut accounts = vec![
+        (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (multisig_pda, Account::new(0, 0, &system_program)),
+        (recipient, Account::new(0, 0, &system_program)),
+        (RENT, rent_sysvar_account(&mollusk)),
+        (system_program, system_account),
+    ];
+    accounts.extend(
+        members
+            .iter()
+            .map(|m| (*m, Account::new(0, 0, &system_program))),
+    );
+
+    let init_res = mollusk.process_and_validate_instruction(
+        &Instruction::new_with_bytes(PROGRAM, &ser_init_ix_data, init_accounts),
+        &accounts,
+        &[
+            Check::success(),
+            Check::account(&multisig_pda)
+                .owner(&PROGRAM)
+                .space(MultisigVaultState::LEN)
+                .build(),
+        ],
+    );
+
+    let mut funded = init_res.resulting_accounts;
+    funded
+        .iter_mut()
+        .find(|(k, _)| *k == multisig_pda)
+        .unwrap()
+        .1
+        .lamports += LAMPORTS_PER_SOL;
+
+    let mut ser_withdraw_ix_data = vec![18];
+    ser_withdraw_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Withdraw {
+            amount: LAMPORTS_PER_SOL,
+        })
+    });
+    let withdraw_instruction = |signers: &[Pubkey]| {
+        let mut ix_accounts = vec![
+            AccountMeta::new(multisig_pda, false),
+            AccountMeta::new(recipient, false),
+            AccountMeta::new_readonly(RENT, false),
+            AccountMeta::new_readonly(system_program, false),
+        ];
+        ix_accounts.extend(signers.iter().map(|s| AccountMeta::new_readonly(*s, true)));
+        Instruction::new_with_bytes(PROGRAM, &ser_withdraw_ix_data, ix_accounts)
+    };
+
+    // The same member passed twice still counts once
+    mollusk.process_and_validate_instruction(
+        &withdraw_instruction(&[members[0], members[0]]),
+        &funded,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::ThresholdNotMet as u32,
+        ))],
+    );
+
+    mollusk.process_and_validate_instruction(
+        &withdraw_instruction(&[members[0], members[2]]),
+        &funded,
+        &[
+            Check::success(),
+            Check::account(&recipient)
+                .lamports(LAMPORTS_PER_SOL)
+                .build(),
+        ],
+    );
+}
+
+/// Program-owned multisig vault account holding `threshold` of `members`.
+fn multisig_vault_account(
+    seed: [u8; 32],
+    bump: u8,
+    threshold: u8,
+    members: &[Pubkey],
+    lamports: u64,
+) -> Account {
+    let mut signers = [[0; 32]; 10];
+    for (signer, member) in signers.iter_mut().zip(members) {
+        *signer = member.to_bytes();
+    }
+    let state = MultisigVaultState {
+        discriminator: 2,
+        version: 1,
+        threshold,
+        signer_count: members.len() as u8,
+        bump,
+        _padding: [0; 3],
+        creator: PAYER.to_bytes(),
+        seed,
+        signers,
+    };
+    let mut account = Account::new(lamports, MultisigVaultState::LEN, &PROGRAM);
+    account.data = unsafe { to_bytes(&state) }.to_vec();
+    account
+}
+
+#[test]
+fn test_multisig_close_requires_threshold() {
+    let mollusk = mollusk();
+
+    let (system_program, _) = program::keyed_account_for_system_program();
+    let seed = [7u8; 32];
+    let (multisig_pda, bump) = Pubkey::find_program_address(
+        &[
+            MultisigVaultState::SEED.as_bytes(),
+            &PAYER.to_bytes(),
+            &seed,
+        ],
+        &PROGRAM,
+    );
+    let members = [
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+    let recipient = Pubkey::new_unique();
+    let vault_lamports = LAMPORTS_PER_SOL;
+
+    let mut accounts = vec![
+        (
+            multisig_pda,
+            multisig_vault_account(seed, bump, 2, &members, vault_lamports),
+        ),
+        (recipient, Account::new(0, 0, &system_program)),
+    ];
+    accounts.extend(
+        members
+            .iter()
+            .map(|m| (*m, Account::new(0, 0, &system_program))),
+    );
+
+    let ser_close_ix_data = vec![19];
+    let close_instruction = |recipient: AccountMeta, signers: &[Pubkey]| {
+        let mut ix_accounts = vec![AccountMeta::new(multisig_pda, false), recipient];
+        ix_accounts.extend(signers.iter().map(|s| AccountMeta::new_readonly(*s, true)));
+        Instruction::new_with_bytes(PROGRAM, &ser_close_ix_data, ix_accounts)
+    };
+
+    // One member is below the threshold of two
+    mollusk.process_and_validate_instruction(
+        &close_instruction(AccountMeta::new(recipient, false), &[members[1]]),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::ThresholdNotMet as u32,
+        ))],
+    );
+
+    // The recipient must be writable and cannot be the vault itself
+    mollusk.process_and_validate_instruction(
+        &close_instruction(
+            AccountMeta::new_readonly(recipient, false),
+            &[members[0], members[1]],
+        ),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::AccountNotWritable as u32,
+        ))],
+    );
+    mollusk.process_and_validate_instruction(
+        &close_instruction(
+            AccountMeta::new(multisig_pda, false),
+            &[members[0], members[1]],
+        ),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::InvalidDestination as u32,
+        ))],
+    );
+
+    mollusk.process_and_validate_instruction(
+        &close_instruction(
+            AccountMeta::new(recipient, false),
+            &[members[0], members[2]],
+        ),
+        &accounts,
+        &[
+            Check::success(),
+            Check::account(&multisig_pda).closed().build(),
+            Check::account(&recipient).lamports(vault_lamports).build(),
+        ],
+    );
+}
+
+#[test]
+fn test_multisig_withdraw_rejects_invalid_recipient() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let seed = [7u8; 32];
+    let (multisig_pda, bump) = Pubkey::find_program_address(
+        &[
+            MultisigVaultState::SEED.as_bytes(),
+            &PAYER.to_bytes(),
+            &seed,
+        ],
+        &PROGRAM,
+    );
+    let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let recipient = Pubkey::new_unique();
+    let rent_exempt_lamports = mollusk
+        .sysvars
+        .rent
+        .minimum_balance(MultisigVaultState::LEN);
+
+    let mut accounts = vec![
+        (
+            multisig_pda,
+            multisig_vault_account(
+                seed,
+                bump,
+                2,
+                &members,
+                rent_exempt_lamports + LAMPORTS_PER_SOL,
+            ),
+        ),
+        (recipient, Account::new(0, 0, &system_program)),
+        (RENT, rent_sysvar_account(&mollusk)),
+        (system_program, system_account),
+    ];
+    accounts.extend(
+        members
+            .iter()
+            .map(|m| (*m, Account::new(0, 0, &system_program))),
+    );
+
+    let mut ser_withdraw_ix_data = vec![18];
+    ser_withdraw_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Withdraw {
+            amount: 100_000_000,
+        })
+    });
+    let withdraw_instruction = |recipient: AccountMeta| {
+        let mut ix_accounts = vec![
+            AccountMeta::new(multisig_pda, false),
+            recipient,
+            AccountMeta::new_readonly(RENT, false),
+            AccountMeta::new_readonly(system_program, false),
+        ];
+        ix_accounts.extend(members.iter().map(|m| AccountMeta::new_readonly(*m, true)));
+        Instruction::new_with_bytes(PROGRAM, &ser_withdraw_ix_data, ix_accounts)
+    };
+
+    mollusk.process_and_validate_instruction(
+        &withdraw_instruction(AccountMeta::new_readonly(recipient, false)),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::AccountNotWritable as u32,
+        ))],
+    );
+
+    mollusk.process_and_validate_instruction(
+        &withdraw_instruction(AccountMeta::new(multisig_pda, false)),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::InvalidDestination as u32,
+        ))],
+    );
+}
+
+#[test]
+fn test_init_multisig_rejects_invalid_members() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let seed = [7u8; 32];
+    let (multisig_pda, _) = Pubkey::find_program_address(
+        &[
+            MultisigVaultState::SEED.as_bytes(),
+            &PAYER.to_bytes(),
+            &seed,
+        ],
+        &PROGRAM,
+    );
+    let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+    let mut accounts = vec![
+        (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (multisig_pda, Account::new(0, 0, &system_program)),
+        (RENT, rent_sysvar_account(&mollusk)),
+        (system_program, system_account),
+    ];
+    accounts.extend(
+        members
+            .iter()
+            .map(|m| (*m, Account::new(0, 0, &system_program))),
+    );
+
+    let init_instruction = |threshold: u8, members: &[Pubkey]| {
+        let mut ser_init_ix_data = vec![17];
+        ser_init_ix_data.extend_from_slice(unsafe { to_bytes(&InitMultisig { seed, threshold }) });
+        let mut ix_accounts = vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(multisig_pda, false),
+            AccountMeta::new_readonly(RENT, false),
+            AccountMeta::new_readonly(system_program, false),
+        ];
+        ix_accounts.extend(members.iter().map(|m| AccountMeta::new_readonly(*m, false)));
+        Instruction::new_with_bytes(PROGRAM, &ser_init_ix_data, ix_accounts)
+    };
+
+    let invalid_threshold = [Check::err(ProgramError::Custom(
+        MyProgramError::InvalidThreshold as u32,
+    ))];
+    mollusk.process_and_validate_instruction(
+        &init_instruction(0, &members),
+        &accounts,
+        &invalid_threshold,
+    );
+    mollusk.process_and_validate_instruction(
+        &init_instruction(3, &members),
+        &accounts,
+        &invalid_threshold,
+    );
+
+    // Members must be distinct
+    mollusk.process_and_validate_instruction(
+        &init_instruction(2, &[members[0], members[0]]),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::DuplicateSigner as u32,
+        ))],
+    );
+}
+
+#[test]
+fn test_vault_survives_ownership_transfer() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+
+    let new_owner = Pubkey::new_unique();
+    let impostor = Pubkey::new_unique();
+
+    let mut ser_propose_ix_data = vec![20];
+    ser_propose_ix_data.extend_from_slice(unsafe { to_bytes(&ProposeOwner) });
+    let propose_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_propose_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_state_pda, false),
+            AccountMeta::new_readonly(new_owner, false),
+        ],
+    );
+
+    let mut ser_accept_ix_data = vec![21];
+    ser_accept_ix_data.extend_from_slice(unsafe { to_bytes(&AcceptOwner) });
+    let accept_instruction = |signer: Pubkey| {
+        Instruction::new_with_bytes(
+            PROGRAM,
+            &ser_accept_ix_data,
+            vec![
+                AccountMeta::new_readonly(signer, true),
+                AccountMeta::new(vault_state_pda, false),
+            ],
+        )
+    };
+
+    let mut ser_withdraw_ix_data = vec![2];
+    ser_withdraw_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Withdraw {
+            amount: 100_000_000,
+        })
+    });
+    let withdraw_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_withdraw_ix_data,
+        vec![
+            AccountMeta::new(new_owner, true),
+            AccountMeta::new(vault_state_pda, false),
+            AccountMeta::new_readonly(RENT, false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+
+    let accounts = [
+        (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (
+            vault_state_pda,
+            vault_account(
+                &VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump),
+                rent_exempt_lamports + LAMPORTS_PER_SOL,
+            ),
+        ),
+        (
+            new_owner,
+            Account::new(LAMPORTS_PER_SOL, 0, &system_program),
+        ),
+        (impostor, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (RENT, rent_sysvar_account(&mollusk)),
+        (system_program, system_account),
+    ];
+
+    // Only the proposed key can accept
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (&propose_instruction, &[Check::success()]),
+            (
+                &accept_instruction(impostor),
+                &[Check::err(ProgramError::Custom(
+                    MyProgramError::InvalidPendingOwner as u32,
+                ))],
+            ),
+        ],
+        &accounts,
+    );
+
+    // The vault keeps its address and answers to the new owner
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (&propose_instruction, &[Check::success()]),
+            (&accept_instruction(new_owner), &[Check::success()]),
+            (
+                &withdraw_instruction,
+                &[
+                    Check::success(),
+                    Check::account(&new_owner)
+                        .lamports(LAMPORTS_PER_SOL + 100_000_000)
+                        .build(),
+                ],
+            ),
+        ],
+        &accounts,
+    );
+}
+
+#[test]
+fn test_init_rejects_non_canonical_pda() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (_, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+
+    // Highest valid bump below the canonical one
+    let other_pda = (0..bump)
+        .rev()
+        .find_map(|b| {
+            Pubkey::create_program_address(
+                &[VaultState::SEED.as_bytes(), &PAYER.to_bytes(), &[b]],
+                &PROGRAM,
+            )
+            .ok()
+        })
+        .unwrap();
+
+    let mut ser_init_ix_data = vec![0];
+    ser_init_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Init {
+            unlock_timestamp: 0,
+            withdraw_cooldown: 0,
+            label: DEFAULT_LABEL,
+        })
+    });
+
+    mollusk.process_and_validate_instruction(
+        &Instruction::new_with_bytes(
+            PROGRAM,
+            &ser_init_ix_data,
+            vec![
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new(other_pda, false),
+                AccountMeta::new_readonly(RENT, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        ),
+        &[
+            (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (other_pda, Account::new(0, 0, &system_program)),
+            (RENT, rent_sysvar_account(&mollusk)),
+            (system_program, system_account),
+        ],
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::PdaMismatch as u32,
+        ))],
+    );
+}
+
+#[test]
+fn test_init_token_vault_rejects_non_canonical_bump() {
+    let mut mollusk = mollusk();
+    mollusk_svm_programs_token::token::add_program(&mut mollusk);
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (token_program, token_program_account) = mollusk_svm_programs_token::token::keyed_account();
+
+    let mint = Pubkey::new_unique();
+    let (_, bump) = Pubkey::find_program_address(
+        &[
+            VaultState::SEED.as_bytes(),
+            &PAYER.to_bytes(),
+            &mint.to_bytes(),
+        ],
+        &PROGRAM,
+    );
+
+    // Highest valid bump below the canonical one
+    let (other_pda, other_bump) = (0..bump)
+        .rev()
+        .find_map(|b| {
+            Pubkey::create_program_address(
+                &[
+                    VaultState::SEED.as_bytes(),
+                    &PAYER.to_bytes(),
+                    &mint.to_bytes(),
+                    &[b],
+                ],
+                &PROGRAM,
+            )
+            .ok()
+            .map(|pda| (pda, b))
+        })
+        .unwrap();
+
+    let mut ser_init_ix_data = vec![4];
+    ser_init_ix_data.extend_from_slice(unsafe { to_bytes(&InitTokenVault { bump: other_bump }) });
+
+    mollusk.process_and_validate_instruction(
+        &Instruction::new_with_bytes(
+            PROGRAM,
+            &ser_init_ix_data,
+            vec![
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new(other_pda, false),
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new_readonly(RENT, false),
+                AccountMeta::new_readonly(system_program, false),
+                AccountMeta::new_readonly(token_program, false),
+            ],
+        ),
+        &[
+            (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (other_pda, Account::new(0, 0, &system_program)),
+            (
+                mint,
+                mollusk_svm_programs_token::token::create_account_for_mint(Mint {
+                    mint_authority: COption::None,
+                    supply: 1_000_000,
+                    decimals: 6,
+                    is_initialized: true,
+                    freeze_authority: COption::None,
+                }),
+            ),
+            (RENT, rent_sysvar_account(&mollusk)),
+            (system_program, system_account),
+            (token_program, token_program_account),
+        ],
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::PdaMismatch as u32,
+        ))],
+    );
+}
+
+/// System program `Transfer` built by hand; the SDK no longer re-exports the builders.
+fn system_transfer_instruction(from: Pubkey, to: Pubkey, lamports: u64) -> Instruction {
+    let mut data = vec![2, 0, 0, 0];
+    data.extend_from_slice(&lamports.to_le_bytes());
+    Instruction::new_with_bytes(
+        Pubkey::default(),
+        &data,
+        vec![AccountMeta::new(from, true), AccountMeta::new(to, false)],
+    )
+}
+
+#[test]
+fn test_closed_vault_cannot_be_revived_in_same_transaction() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+
+    let mut ser_close_ix_data = vec![3];
+    ser_close_ix_data.extend_from_slice(unsafe { to_bytes(&Close) });
+    let close_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_close_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_state_pda, false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+
+    // Refund the address right after close, as an attacker bundling instructions would
+    let revive_instruction =
+        system_transfer_instruction(PAYER, vault_state_pda, rent_exempt_lamports);
+
+    let mut ser_deposit_ix_data = vec![1];
+    ser_deposit_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Deposit {
+            amount: 100_000_000,
+        })
+    });
+    let deposit_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_deposit_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_state_pda, false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (
+                &close_instruction,
+                &[
+                    Check::success(),
+                    Check::account(&vault_state_pda)
+                        .lamports(0)
+                        .owner(&system_program)
+                        .space(0)
+                        .build(),
+                ],
+            ),
+            (
+                &revive_instruction,
+                &[
+                    Check::success(),
+                    Check::account(&vault_state_pda)
+                        .owner(&system_program)
+                        .space(0)
+                        .build(),
+                ],
+            ),
+            (
+                &deposit_instruction,
+                &[Check::err(ProgramError::Custom(
+                    MyProgramError::VaultNotInitialized as u32,
+                ))],
+            ),
+        ],
+        &[
+            (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (
+                vault_state_pda,
+                vault_account(
+                    &VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump),
+                    rent_exempt_lamports,
+                ),
+            ),
+            (system_program, system_account),
+        ],
+    );
+}
+
+#[test]
+fn test_close_sweeps_into_destination() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+    let treasury = Pubkey::new_unique();
+
+    let mut ser_close_ix_data = vec![3];
+    ser_close_ix_data.extend_from_slice(unsafe { to_bytes(&Close) });
+    let close_instruction = |destination: Pubkey| {
+        Instruction::new_with_bytes(
+            PROGRAM,
+            &ser_close_ix_data,
+            vec![
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new(vault_state_pda, false),
+                AccountMeta::new_readonly(system_program, false),
+                AccountMeta::new(destination, false),
+            ],
+        )
+    };
+
+    let accounts = [
+        (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (
+            vault_state_pda,
+            vault_account(
+                &VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump),
+                rent_exempt_lamports + LAMPORTS_PER_SOL,
+            ),
+        ),
+        (system_program, system_account),
+        (treasury, Account::new(0, 0, &system_program)),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &close_instruction(treasury),
+        &accounts,
+        &[
+            Check::success(),
+            Check::account(&treasury)
+                .lamports(rent_exempt_lamports + LAMPORTS_PER_SOL)
+                .build(),
+            Check::account(&PAYER).lamports(LAMPORTS_PER_SOL).build(),
+        ],
+    );
+
+    // The vault can't be its own destination
+    mollusk.process_and_validate_instruction(
+        &close_instruction(vault_state_pda),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::InvalidDestination as u32,
+        ))],
+    );
+}
+
+#[test]
+fn test_withdraw_pays_recipient() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+    let recipient = Pubkey::new_unique();
+
+    let mut ser_withdraw_ix_data = vec![2];
+    ser_withdraw_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Withdraw {
+            amount: LAMPORTS_PER_SOL,
+        })
+    });
+    let withdraw_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_withdraw_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_state_pda, false),
+            AccountMeta::new_readonly(RENT, false),
+            AccountMeta::new_readonly(system_program, false),
+            AccountMeta::new(recipient, false),
+        ],
+    );
+
+    let accounts = [
+        (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (
+            vault_state_pda,
+            vault_account(
+                &VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump),
+                rent_exempt_lamports + LAMPORTS_PER_SOL,
+            ),
+        ),
+        (RENT, rent_sysvar_account(&mollusk)),
+        (system_program, system_account),
+        (recipient, Account::new(0, 0, &system_program)),
+    ];
+
+    let withdraw_res = mollusk.process_and_validate_instruction(
+        &withdraw_instruction,
+        &accounts,
+        &[
+            Check::success(),
+            Check::account(&recipient)
+                .lamports(LAMPORTS_PER_SOL)
+                .build(),
+            Check::account(&PAYER).lamports(LAMPORTS_PER_SOL).build(),
+            Check::account(&vault_state_pda)
+                .lamports(rent_exempt_lamports)
+                .build(),
+        ],
+    );
+
+    // The rent reserve is still protected when paying someone else: the vault holds
+    // the lamport, but paying it out would leave it below the rent-exempt minimum
+    let mut ser_drain_ix_data = vec![2];
+    ser_drain_ix_data.extend_from_slice(unsafe { to_bytes(&Withdraw { amount: 1 }) });
+    let drain_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_drain_ix_data,
+        withdraw_instruction.accounts.clone(),
+    );
+    mollusk.process_and_validate_instruction(
+        &drain_instruction,
+        &withdraw_res.resulting_accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::BelowRentMinimum as u32,
+        ))],
+    );
+}
+
+#[test]
+fn test_withdraw_all_leaves_rent_exempt_minimum() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+    let balance = 1_234_567_890;
+
+    let mut ser_withdraw_all_ix_data = vec![22];
+    ser_withdraw_all_ix_data.extend_from_slice(unsafe { to_bytes(&WithdrawAll) });
+
+    mollusk.process_and_validate_instruction(
+        &Instruction::new_with_bytes(
+            PROGRAM,
+            &ser_withdraw_all_ix_data,
+            vec![
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new(vault_state_pda, false),
+                AccountMeta::new_readonly(RENT, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        ),
+        &[
+            (PAYER, Account::new(0, 0, &system_program)),
+            (
+                vault_state_pda,
+                vault_account(
+                    &VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump),
+                    rent_exempt_lamports + balance,
+                ),
+            ),
+            (RENT, rent_sysvar_account(&mollusk)),
+            (system_program, system_account),
+        ],
+        &[
+            Check::success(),
+            Check::return_data(&balance.to_le_bytes()),
+            Check::account(&PAYER).lamports(balance).build(),
+            Check::account(&vault_state_pda)
+                .lamports(rent_exempt_lamports)
+                .build(),
+        ],
+    );
+}
+
+#[test]
+fn test_third_party_deposit_respects_policy() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+    let payroll = Pubkey::new_unique();
+
+    let mut ser_deposit_ix_data = vec![1];
+    ser_deposit_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Deposit {
+            amount: 100_000_000,
+        })
+    });
+    let deposit_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_deposit_ix_data,
+        vec![
+            AccountMeta::new(payroll, true),
+            AccountMeta::new(vault_state_pda, false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+
+    let mut ser_policy_ix_data = vec![23];
+    ser_policy_ix_data.extend_from_slice(unsafe { to_bytes(&SetDepositPolicy { owner_only: 1 }) });
+    let policy_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_policy_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_state_pda, false),
+        ],
+    );
+
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (
+                &deposit_instruction,
+                &[
+                    Check::success(),
+                    Check::account(&vault_state_pda)
+                        .lamports(rent_exempt_lamports + 100_000_000)
+                        .build(),
+                ],
+            ),
+            (&policy_instruction, &[Check::success()]),
+            (
+                &deposit_instruction,
+                &[Check::err(ProgramError::Custom(
+                    MyProgramError::InvalidOwner as u32,
+                ))],
+            ),
+        ],
+        &[
+            (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (payroll, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (
+                vault_state_pda,
+                vault_account(
+                    &VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump),
+                    rent_exempt_lamports,
+                ),
+            ),
+            (system_program, system_account),
+        ],
+    );
+}
+
+#[test]
+fn test_init_with_deposit() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+    let deposit_amount = 250_000_000;
+
+    let mut ser_ix_data = vec![24];
+    ser_ix_data.extend_from_slice(unsafe {
+        to_bytes(&InitWithDeposit {
+            unlock_timestamp: 0,
+            withdraw_cooldown: 0,
+            label: DEFAULT_LABEL,
+            amount: deposit_amount,
+        })
+    });
+
+    mollusk.process_and_validate_instruction(
+        &Instruction::new_with_bytes(
+            PROGRAM,
+            &ser_ix_data,
+            vec![
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new(vault_state_pda, false),
+                AccountMeta::new_readonly(RENT, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        ),
+        &[
+            (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (vault_state_pda, Account::new(0, 0, &system_program)),
+            (RENT, rent_sysvar_account(&mollusk)),
+            (system_program, system_account),
+        ],
+        &[
+            Check::success(),
+            Check::account(&vault_state_pda)
+                .owner(&PROGRAM)
+                .lamports(rent_exempt_lamports + deposit_amount)
+                .data(unsafe { to_bytes(&VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump)) })
+                .build(),
+            Check::account(&PAYER)
+                .lamports(LAMPORTS_PER_SOL - rent_exempt_lamports - deposit_amount)
+                .build(),
+        ],
+    );
+}
+
+#[test]
+fn test_init_idempotent_retries_and_prefunded_address() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+    let griefing_lamports = 1_000;
+
+    let mut ser_ix_data = vec![25];
+    ser_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Init {
+            unlock_timestamp: 0,
+            withdraw_cooldown: 0,
+            label: DEFAULT_LABEL,
+        })
+    });
+    let instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_state_pda, false),
+            AccountMeta::new_readonly(RENT, false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+
+    let vault_state = VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump);
+    let initialized = [
+        Check::success(),
+        Check::account(&vault_state_pda)
+            .owner(&PROGRAM)
+            .lamports(rent_exempt_lamports)
+            .data(unsafe { to_bytes(&vault_state) })
+            .build(),
+        Check::account(&PAYER)
+            .lamports(LAMPORTS_PER_SOL - (rent_exempt_lamports - griefing_lamports))
+            .build(),
+    ];
+
+    // Lamports sent to the address before init are topped up, and the retry is a no-op
+    mollusk.process_and_validate_instruction_chain(
+        &[(&instruction, &initialized), (&instruction, &initialized)],
+        &[
+            (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (
+                vault_state_pda,
+                Account::new(griefing_lamports, 0, &system_program),
+            ),
+            (RENT, rent_sysvar_account(&mollusk)),
+            (system_program, system_account),
+        ],
+    );
+}
+
+#[test]
+fn test_init_idempotent_rejects_other_existing_vaults() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+
+    // A vault PAYER owns only through `AcceptOwner`; its creator is someone else
+    let creator = Pubkey::new_unique();
+    let (transferred_pda, transferred_bump) = Pubkey::find_program_address(
+        &[VaultState::SEED.as_bytes(), &creator.to_bytes()],
+        &PROGRAM,
+    );
+    let mut transferred_state =
+        VaultState::new(creator.to_bytes(), DEFAULT_LABEL, transferred_bump);
+    transferred_state.owner = PAYER.to_bytes();
+
+    // PAYER's own vault, but under another label
+    let label = vault_label("savings");
+    let (labeled_pda, labeled_bump) = Pubkey::find_program_address(
+        &[
+            VaultState::LABELED_SEED.as_bytes(),
+            &PAYER.to_bytes(),
+            &label,
+        ],
+        &PROGRAM,
+    );
+    let labeled_state = VaultState::new(PAYER.to_bytes(), label, labeled_bump);
+
+    let mut ser_ix_data = vec![25];
+    ser_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Init {
+            unlock_timestamp: 0,
+            withdraw_cooldown: 0,
+            label: DEFAULT_LABEL,
+        })
+    });
+
+    for (vault_pda, vault_state) in [
+        (transferred_pda, transferred_state),
+        (labeled_pda, labeled_state),
+    ] {
+        mollusk.process_and_validate_instruction(
+            &Instruction::new_with_bytes(
+                PROGRAM,
+                &ser_ix_data,
+                vec![
+                    AccountMeta::new(PAYER, true),
+                    AccountMeta::new(vault_pda, false),
+                    AccountMeta::new_readonly(RENT, false),
+                    AccountMeta::new_readonly(system_program, false),
+                ],
+            ),
+            &[
+                (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+                (vault_pda, vault_account(&vault_state, rent_exempt_lamports)),
+                (RENT, rent_sysvar_account(&mollusk)),
+                (system_program, system_account.clone()),
+            ],
+            &[Check::err(ProgramError::Custom(
+                MyProgramError::PdaMismatch as u32,
+            ))],
+        );
+    }
+}
+
+#[test]
+fn test_init_handles_prefunded_pda() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+
+    let mut ser_ix_data = vec![0];
+    ser_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Init {
+            unlock_timestamp: 0,
+            withdraw_cooldown: 0,
+            label: DEFAULT_LABEL,
+        })
+    });
+
+    // Someone sent more than the rent-exempt minimum to the address before init,
+    // which used to make `CreateAccount` fail forever
+    let vault_state = VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump);
+    mollusk.process_and_validate_instruction(
+        &Instruction::new_with_bytes(
+            PROGRAM,
+            &ser_ix_data,
+            vec![
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new(vault_state_pda, false),
+                AccountMeta::new_readonly(RENT, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        ),
+        &[
+            (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (
+                vault_state_pda,
+                Account::new(2 * LAMPORTS_PER_SOL, 0, &system_program),
+            ),
+            (RENT, rent_sysvar_account(&mollusk)),
+            (system_program, system_account),
+        ],
+        &[
+            Check::success(),
+            Check::account(&vault_state_pda)
+                .owner(&PROGRAM)
+                .lamports(2 * LAMPORTS_PER_SOL)
+                .data(unsafe { to_bytes(&vault_state) })
+                .build(),
+            Check::account(&PAYER).lamports(LAMPORTS_PER_SOL).build(),
+        ],
+    );
+}
+
+#[test]
+fn test_init_with_deposit_on_prefunded_pda_transfers_full_amount() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let deposit_amount = 250_000_000;
+
+    let mut ser_ix_data = vec![24];
+    ser_ix_data.extend_from_slice(unsafe {
+        to_bytes(&InitWithDeposit {
+            unlock_timestamp: 0,
+            withdraw_cooldown: 0,
+            label: DEFAULT_LABEL,
+            amount: deposit_amount,
+        })
+    });
+
+    // Lamports already at the address cover rent but are not part of the deposit
+    let vault_state = VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump);
+    mollusk.process_and_validate_instruction(
+        &Instruction::new_with_bytes(
+            PROGRAM,
+            &ser_ix_data,
+            vec![
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new(vault_state_pda, false),
+                AccountMeta::new_readonly(RENT, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        ),
+        &[
+            (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (
+                vault_state_pda,
+                Account::new(2 * LAMPORTS_PER_SOL, 0, &system_program),
+            ),
+            (RENT, rent_sysvar_account(&mollusk)),
+            (system_program, system_account),
+        ],
+        &[
+            Check::success(),
+            Check::account(&vault_state_pda)
+                .owner(&PROGRAM)
+                .lamports(2 * LAMPORTS_PER_SOL + deposit_amount)
+                .data(unsafe { to_bytes(&vault_state) })
+                .build(),
+            Check::account(&PAYER)
+                .lamports(LAMPORTS_PER_SOL - deposit_amount)
+                .build(),
+        ],
+    );
+}
+
+/// Zero-padded 32-byte vault label.
+fn vault_label(name: &str) -> [u8; 32] {
+    let mut label = [0; 32];
+    label[..name.len()].copy_from_slice(name.as_bytes());
+    label
+}
+
+#[test]
+fn test_named_vaults_per_owner() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let mut accounts = vec![
+        (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (RENT, rent_sysvar_account(&mollusk)),
+        (system_program, system_account),
+    ];
+    let mut instructions = vec![];
+    let mut expected = vec![];
+
+    for name in ["rent", "savings", "taxes"] {
+        let label = vault_label(name);
+        let (vault_pda, bump) = Pubkey::find_program_address(
+            &[
+                VaultState::LABELED_SEED.as_bytes(),
+                &PAYER.to_bytes(),
+                &label,
+            ],
+            &PROGRAM,
+        );
+
+        let mut ser_ix_data = vec![0];
+        ser_ix_data.extend_from_slice(unsafe {
+            to_bytes(&Init {
+                unlock_timestamp: 0,
+                withdraw_cooldown: 0,
+                label,
+            })
+        });
+        instructions.push(Instruction::new_with_bytes(
+            PROGRAM,
+            &ser_ix_data,
+            vec![
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new(vault_pda, false),
+                AccountMeta::new_readonly(RENT, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        ));
+        accounts.push((vault_pda, Account::new(0, 0, &system_program)));
+        expected.push((vault_pda, VaultState::new(PAYER.to_bytes(), label, bump)));
+    }
+
+    let checks: Vec<Vec<Check>> = expected
+        .iter()
+        .map(|(vault_pda, vault_state)| {
+            vec![
+                Check::success(),
+                Check::account(vault_pda)
+                    .owner(&PROGRAM)
+                    .data(unsafe { to_bytes(vault_state) })
+                    .build(),
+            ]
+        })
+        .collect();
+    let chain: Vec<(&Instruction, &[Check])> = instructions
+        .iter()
+        .zip(checks.iter())
+        .map(|(ix, checks)| (ix, checks.as_slice()))
+        .collect();
+
+    mollusk.process_and_validate_instruction_chain(&chain, &accounts);
+
+    // A label only maps to its own address
+    let mut ser_ix_data = vec![0];
+    ser_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Init {
+            unlock_timestamp: 0,
+            withdraw_cooldown: 0,
+            label: vault_label("savings"),
+        })
+    });
+    let mut wrong_label = instructions[0].clone();
+    wrong_label.data = ser_ix_data;
+    mollusk.process_and_validate_instruction(
+        &wrong_label,
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::PdaMismatch as u32,
+        ))],
+    );
+}
+
+#[test]
+fn test_event_log_format() {
+    let vault = [0xab; 32];
+    let mut owner = [0; 32];
+    owner[31] = 0x0f;
+
+    let logger = pinocchio_vault::events::VaultEvent {
+        instruction: ProgramInstruction::Withdraw,
+        vault: &vault,
+        owner: &owner,
+        amount: 1_000,
+        balance: 42,
+    }
+    .to_logger();
+
+    let expected = format!(
+        "vault_event ix=withdraw vault={} owner={}0f amount=1000 balance=42",
+        "ab".repeat(32),
+        "00".repeat(31),
+    );
+    assert_eq!(&logger[..], expected.as_bytes());
+}
+
+#[test]
+fn test_event_authority_is_canonical_pda() {
+    let (event_authority, bump) =
+        Pubkey::find_program_address(&[pinocchio_vault::events::EVENT_AUTHORITY_SEED], &PROGRAM);
+    assert_eq!(
+        event_authority.to_bytes(),
+        pinocchio_vault::events::EVENT_AUTHORITY
+    );
+    assert_eq!(bump, pinocchio_vault::events::EVENT_AUTHORITY_BUMP);
+}
+
+#[test]
+fn test_deposit_emits_event_via_self_cpi() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let event_authority = Pubkey::new_from_array(pinocchio_vault::events::EVENT_AUTHORITY);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+
+    let mut ser_deposit_ix_data = vec![1];
+    ser_deposit_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Deposit {
+            amount: 100_000_000,
+        })
+    });
+    let deposit_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_deposit_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_state_pda, false),
+            AccountMeta::new_readonly(system_program, false),
+            AccountMeta::new_readonly(event_authority, false),
+            AccountMeta::new_readonly(PROGRAM, false),
+        ],
+    );
+
+    let accounts = vec![
+        (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (
+            vault_state_pda,
+            vault_account(
+                &VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump),
+                rent_exempt_lamports,
+            ),
+        ),
+        (system_program, system_account),
+        (event_authority, Account::default()),
+        (PROGRAM, program::create_program_account_loader_v3(&PROGRAM)),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &deposit_instruction,
+        &accounts,
+        &[
+            Check::success(),
+            Check::account(&vault_state_pda)
+                .lamports(rent_exempt_lamports + 100_000_000)
+                .build(),
+        ],
+    );
+
+    // The event authority can't sign a top-level EmitEvent, so events can't be forged
+    let vault = VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump);
+    let mut forged_ix_data = vec![26];
+    forged_ix_data.extend_from_slice(
+        &pinocchio_vault::events::VaultEvent {
+            instruction: ProgramInstruction::Deposit,
+            vault: &vault_state_pda.to_bytes(),
+            owner: &vault.owner,
+            amount: 1,
+            balance: 1,
+        }
+        .to_bytes(),
+    );
+    let forged_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &forged_ix_data,
+        vec![AccountMeta::new_readonly(event_authority, false)],
+    );
+    mollusk.process_and_validate_instruction(
+        &forged_instruction,
+        &[(event_authority, Account::default())],
+        &[Check::err(ProgramError::MissingRequiredSignature)],
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_decode_emitted_event() {
+    use pinocchio_vault::events::{DecodedEvent, EventDecodeError, VaultEvent};
+
+    let vault = [1; 32];
+    let owner = [2; 32];
+    let event = VaultEvent {
+        instruction: ProgramInstruction::WithdrawAll,
+        vault: &vault,
+        owner: &owner,
+        amount: 7,
+        balance: 890_880,
+    };
+
+    let mut ix_data = vec![ProgramInstruction::EmitEvent as u8];
+    ix_data.extend_from_slice(&event.to_bytes());
+
+    assert_eq!(
+        DecodedEvent::decode(&ix_data),
+        Ok(DecodedEvent {
+            instruction: ProgramInstruction::WithdrawAll,
+            vault,
+            owner,
+            amount: 7,
+            balance: 890_880,
+        })
+    );
+    assert_eq!(
+        DecodedEvent::decode(&ix_data[..40]),
+        Err(EventDecodeError::InvalidLength(39))
+    );
+    assert_eq!(
+        DecodedEvent::decode(&[1, 0, 0, 0, 0, 0, 0, 0, 0]),
+        Err(EventDecodeError::NotAnEvent)
+    );
+}
+
+#[test]
+fn test_error_codes_are_stable() {
+    // Codes are part of the client interface; a failure here means a variant was
+    // renumbered or inserted instead of appended.
+    let codes = [
+        (MyProgramError::InvalidInstructionData, 0),
+        (MyProgramError::PdaMismatch, 1),
+        (MyProgramError::InvalidOwner, 2),
+        (MyProgramError::InvalidDiscriminator, 3),
+        (MyProgramError::UnsupportedVersion, 4),
+        (MyProgramError::InvalidProgramOwner, 5),
+        (MyProgramError::InvalidAccountLength, 6),
+        (MyProgramError::AccountNotWritable, 7),
+        (MyProgramError::InvalidTokenProgram, 8),
+        (MyProgramError::InvalidTokenAccount, 9),
+        (MyProgramError::InvalidMint, 10),
+        (MyProgramError::VaultLocked, 11),
+        (MyProgramError::LockShortened, 12),
+        (MyProgramError::WithdrawRequestRequired, 13),
+        (MyProgramError::WithdrawalAlreadyPending, 14),
+        (MyProgramError::NoPendingWithdrawal, 15),
+        (MyProgramError::CooldownActive, 16),
+        (MyProgramError::CooldownShortened, 17),
+        (MyProgramError::WithdrawLimitExceeded, 18),
+        (MyProgramError::WithdrawLimitLoosened, 19),
+        (MyProgramError::InvalidDelegate, 20),
+        (MyProgramError::InvalidDestination, 21),
+        (MyProgramError::DelegationExpired, 22),
+        (MyProgramError::AllowanceExceeded, 23),
+        (MyProgramError::InvalidThreshold, 24),
+        (MyProgramError::DuplicateSigner, 25),
+        (MyProgramError::ThresholdNotMet, 26),
+        (MyProgramError::InvalidPendingOwner, 27),
+        (MyProgramError::InvalidEventAuthority, 28),
+        (MyProgramError::ZeroAmount, 29),
+        (MyProgramError::InsufficientVaultBalance, 30),
+        (MyProgramError::BelowRentMinimum, 31),
+        (MyProgramError::VaultNotInitialized, 32),
+        (MyProgramError::VaultAlreadyInitialized, 33),
+        (MyProgramError::WrongSystemProgram, 34),
+        (MyProgramError::InvalidRentSysvar, 35),
+        (MyProgramError::TooManyAccounts, 36),
+        (MyProgramError::UnexpectedTokenBalance, 37),
+    ];
+    for (error, code) in codes {
+        assert_eq!(error as u32, code, "{error:?}");
+    }
+}
+
+#[test]
+fn test_deposit_reports_specific_errors() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, _) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+
+    let deposit_instruction = |amount: u64| {
+        let mut ser_deposit_ix_data = vec![1];
+        ser_deposit_ix_data.extend_from_slice(unsafe { to_bytes(&Deposit { amount }) });
+        Instruction::new_with_bytes(
+            PROGRAM,
+            &ser_deposit_ix_data,
+            vec![
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new(vault_state_pda, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        )
+    };
+
+    let accounts = vec![
+        (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (vault_state_pda, Account::new(0, 0, &system_program)),
+        (system_program, system_account),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &deposit_instruction(0),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::ZeroAmount as u32,
+        ))],
+    );
+    mollusk.process_and_validate_instruction(
+        &deposit_instruction(100_000_000),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::VaultNotInitialized as u32,
+        ))],
+    );
+}
+
+#[test]
+fn test_extra_trailing_accounts_are_rejected() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+    let first = Pubkey::new_unique();
+    let second = Pubkey::new_unique();
+
+    let mut ser_withdraw_ix_data = vec![2];
+    ser_withdraw_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Withdraw {
+            amount: 100_000_000,
+        })
+    });
+    let mut ser_close_ix_data = vec![3];
+    ser_close_ix_data.extend_from_slice(unsafe { to_bytes(&Close) });
+
+    let accounts = [
+        (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (
+            vault_state_pda,
+            vault_account(
+                &VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump),
+                rent_exempt_lamports + LAMPORTS_PER_SOL,
+            ),
+        ),
+        (RENT, rent_sysvar_account(&mollusk)),
+        (system_program, system_account),
+        (first, Account::new(0, 0, &system_program)),
+        (second, Account::new(0, 0, &system_program)),
+    ];
+
+    // Withdraw and Close take at most one recipient
+    for instruction in [
+        Instruction::new_with_bytes(
+            PROGRAM,
+            &ser_withdraw_ix_data,
+            vec![
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new(vault_state_pda, false),
+                AccountMeta::new_readonly(RENT, false),
+                AccountMeta::new_readonly(system_program, false),
+                AccountMeta::new(first, false),
+                AccountMeta::new(second, false),
+            ],
+        ),
+        Instruction::new_with_bytes(
+            PROGRAM,
+            &ser_close_ix_data,
+            vec![
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new(vault_state_pda, false),
+                AccountMeta::new_readonly(system_program, false),
+                AccountMeta::new(first, false),
+                AccountMeta::new(second, false),
+            ],
+        ),
+    ] {
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &accounts,
+            &[Check::err(ProgramError::Custom(
+                MyProgramError::TooManyAccounts as u32,
+            ))],
+        );
+    }
+}
+
+#[test]
+fn test_withdraw_rejects_wrong_program_and_sysvar_accounts() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+    let impostor = Pubkey::new_unique();
+
+    let mut ser_withdraw_ix_data = vec![2];
+    ser_withdraw_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Withdraw {
+            amount: 100_000_000,
+        })
+    });
+    let withdraw_instruction = |rent: Pubkey, system: Pubkey| {
+        Instruction::new_with_bytes(
+            PROGRAM,
+            &ser_withdraw_ix_data,
+            vec![
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new(vault_state_pda, false),
+                AccountMeta::new_readonly(rent, false),
+                AccountMeta::new_readonly(system, false),
+            ],
+        )
+    };
+
+    let accounts = [
+        (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (
+            vault_state_pda,
+            vault_account(
+                &VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump),
+                rent_exempt_lamports + LAMPORTS_PER_SOL,
+            ),
+        ),
+        (RENT, rent_sysvar_account(&mollusk)),
+        (system_program, system_account),
+        (impostor, Account::new(0, 0, &system_program)),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &withdraw_instruction(RENT, impostor),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::WrongSystemProgram as u32,
+        ))],
+    );
+    mollusk.process_and_validate_instruction(
+        &withdraw_instruction(impostor, system_program),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::InvalidRentSysvar as u32,
+        ))],
+    );
+    mollusk.process_and_validate_instruction(
+        &withdraw_instruction(RENT, system_program),
+        &accounts,
+        &[Check::success()],
+    );
+}
+
+#[test]
+fn test_v2_instructions_read_rent_from_syscall() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault_state_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let rent_exempt_lamports = mollusk.sysvars.rent.minimum_balance(VaultState::LEN);
+
+    let mut ser_init_ix_data = vec![27];
+    ser_init_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Init {
+            unlock_timestamp: 0,
+            withdraw_cooldown: 0,
+            label: DEFAULT_LABEL,
+        })
+    });
+    let init_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_init_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_state_pda, false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+
+    let mut ser_deposit_ix_data = vec![1];
+    ser_deposit_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Deposit {
+            amount: 500_000_000,
+        })
+    });
+    let deposit_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_deposit_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_state_pda, false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+
+    let mut ser_withdraw_ix_data = vec![28];
+    ser_withdraw_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Withdraw {
+            amount: 500_000_000,
+        })
+    });
+    let withdraw_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_withdraw_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_state_pda, false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+
+    let mut ser_drain_ix_data = vec![28];
+    ser_drain_ix_data.extend_from_slice(unsafe { to_bytes(&Withdraw { amount: 1 }) });
+    let drain_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_drain_ix_data,
+        withdraw_instruction.accounts.clone(),
+    );
+
+    let vault_state = VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump);
+
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (
+                &init_instruction,
+                &[
+                    Check::success(),
+                    Check::account(&vault_state_pda)
+                        .lamports(rent_exempt_lamports)
+                        .data(unsafe { to_bytes(&vault_state) })
+                        .build(),
+                ],
+            ),
+            (&deposit_instruction, &[Check::success()]),
+            (
+                &withdraw_instruction,
+                &[
+                    Check::success(),
+                    Check::account(&vault_state_pda)
+                        .lamports(rent_exempt_lamports)
+                        .build(),
+                ],
+            ),
+            // The rent reserve read through the syscall is still enforced
+            (
+                &drain_instruction,
+                &[Check::err(ProgramError::Custom(
+                    MyProgramError::BelowRentMinimum as u32,
+                ))],
+            ),
+        ],
+        &[
+            (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (vault_state_pda, Account::new(0, 0, &system_program)),
+            (system_program, system_account),
+        ],
+    );
+}
+
+#[test]
+fn test_close_v0_drains_baseline_vault() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    // Vaults from the first deployment store only the owner and use a caller-supplied bump.
+    let (legacy_vault, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let mut legacy_account = Account::new(500_000_000, VaultState::V0_LEN, &PROGRAM);
+    legacy_account.data = PAYER.to_bytes().to_vec();
+
+    let mut ser_close_ix_data = vec![29];
+    ser_close_ix_data.extend_from_slice(unsafe { to_bytes(&CloseV0 { bump }) });
+    let close_instruction = |user: Pubkey| {
+        Instruction::new_with_bytes(
+            PROGRAM,
+            &ser_close_ix_data,
+            vec![
+                AccountMeta::new(user, true),
+                AccountMeta::new(legacy_vault, false),
+            ],
+        )
+    };
+
+    let other = Pubkey::new_unique();
+    let accounts = [
+        (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (other, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (legacy_vault, legacy_account),
+        (system_program, system_account),
+    ];
+
+    // The current loaders reject the 32-byte layout
+    let ser_close_vault_ix_data = vec![3];
+    mollusk.process_and_validate_instruction(
+        &Instruction::new_with_bytes(
+            PROGRAM,
+            &ser_close_vault_ix_data,
+            vec![
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new(legacy_vault, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        ),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::InvalidAccountLength as u32,
+        ))],
+    );
+
+    // Only the owner the address was derived from can close it
+    mollusk.process_and_validate_instruction(
+        &close_instruction(other),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::PdaMismatch as u32,
+        ))],
+    );
+
+    mollusk.process_and_validate_instruction(
+        &close_instruction(PAYER),
+        &accounts,
+        &[
+            Check::success(),
+            Check::account(&PAYER)
+                .lamports(LAMPORTS_PER_SOL + 500_000_000)
+                .build(),
+            Check::account(&legacy_vault).closed().build(),
+        ],
+    );
+}
+
+#[test]
+fn test_default_vault_reuses_baseline_address() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    // The unlabelled vault keeps the `["vault", owner]` address of the first deployment
+    let (vault_pda, bump) =
+        Pubkey::find_program_address(&[VaultState::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
+    let mut legacy_account = Account::new(500_000_000, VaultState::V0_LEN, &PROGRAM);
+    legacy_account.data = PAYER.to_bytes().to_vec();
+
+    let mut ser_close_ix_data = vec![29];
+    ser_close_ix_data.extend_from_slice(unsafe { to_bytes(&CloseV0 { bump }) });
+    let close_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_close_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_pda, false),
+        ],
+    );
+
+    let mut ser_init_ix_data = vec![0];
+    ser_init_ix_data.extend_from_slice(unsafe {
+        to_bytes(&Init {
+            unlock_timestamp: 0,
+            withdraw_cooldown: 0,
+            label: DEFAULT_LABEL,
+        })
+    });
+    let init_instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_init_ix_data,
+        vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(vault_pda, false),
+            AccountMeta::new_readonly(RENT, false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+
+    let vault_state = VaultState::new(PAYER.to_bytes(), DEFAULT_LABEL, bump);
+
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (&close_instruction, &[Check::success()]),
+            (
+                &init_instruction,
+                &[
+                    Check::success(),
+                    Check::account(&vault_pda)
+                        .data(unsafe { to_bytes(&vault_state) })
+                        .build(),
+                ],
+            ),
+        ],
+        &[
+            (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (vault_pda, legacy_account),
+            (RENT, rent_sysvar_account(&mollusk)),
+            (system_program, system_account),
+        ],
+    );
+}